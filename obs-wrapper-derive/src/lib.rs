@@ -0,0 +1,497 @@
+//! Derive macros for [`obs-wrapper`](https://docs.rs/obs-wrapper).
+//!
+//! Use the re-exports in `obs_wrapper` (enabled with the `derive` feature)
+//! instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::Parse, parse_macro_input, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
+    Expr, Fields, Ident, LitStr, Token, Type,
+};
+
+/// Implements `obs_wrapper::settings::ObsSettings` for a struct with named
+/// fields, so its properties, defaults and parsing share the same keys.
+///
+/// Every field becomes one setting. The kind of property is picked from the
+/// field type: `bool`, integers, `f32`/`f64` and `String`/`ObsString` are
+/// supported, other fields must be marked with `#[obs(skip)]`.
+///
+/// Field attributes, all inside `#[obs(...)]`:
+/// * `name = "key"`: settings key, the field name by default.
+/// * `description = "..."`: text shown in the UI, the key by default.
+/// * `tooltip = "..."`: long description of the property.
+/// * `default = expr`: default value, [`Default::default`] otherwise (an
+///   empty string for text fields).
+/// * `min = expr`, `max = expr`, `step = expr`, `slider`, `suffix = "..."`:
+///   for number fields. A missing bound of an integer defaults to the
+///   smallest or largest value of its type which fits into a `c_int`.
+/// * `password`, `multiline`: for text fields.
+/// * `path = "file" | "file_save" | "directory"`, `filter = "..."`: shows a
+///   path picker for a text field.
+/// * `list("Label" = value, ...)`: shows a list of choices for an integer,
+///   float or text field.
+/// * `color`, `color_alpha`: shows a color picker for an integer field.
+/// * `skip`: not a setting, always [`Default::default`].
+///
+/// Attributes which don't apply to the field fail to compile, like
+/// `password` on an integer or `min` on a list, as do integer literals for
+/// `min`, `max` or `step` which don't fit into a `c_int`, and two fields
+/// with the same key.
+///
+/// ```ignore
+/// #[derive(ObsSettings)]
+/// struct Settings {
+///     #[obs(description = "Canvas Width", default = 1280, min = 100, max = 3840)]
+///     width: u32,
+///     #[obs(description = "Speech Threshold", default = 0.15, max = 1.0, step = 0.01, slider)]
+///     speech_threshold: f32,
+///     #[obs(list("Keyboard" = "keyboard", "Standard" = "standard"), default = "keyboard".to_string())]
+///     mode: String,
+/// }
+/// ```
+#[proc_macro_derive(ObsSettings, attributes(obs))]
+pub fn derive_obs_settings(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_obs_settings(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Bool,
+    Int,
+    Float,
+    Text,
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    Some(path.path.segments.last()?.ident.to_string())
+}
+
+impl Kind {
+    fn of(ty: &Type) -> Option<Self> {
+        match type_name(ty)?.as_str() {
+            "bool" => Some(Kind::Bool),
+            "i8" | "i16" | "i32" | "i64" | "isize" | "u8" | "u16" | "u32" | "u64" | "usize" => {
+                Some(Kind::Int)
+            }
+            "f32" | "f64" => Some(Kind::Float),
+            "String" | "ObsString" => Some(Kind::Text),
+            _ => None,
+        }
+    }
+}
+
+struct Choice {
+    label: LitStr,
+    value: Expr,
+}
+
+impl Parse for Choice {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let label = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { label, value })
+    }
+}
+
+#[derive(Default)]
+struct FieldAttrs {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+    tooltip: Option<LitStr>,
+    default: Option<Expr>,
+    min: Option<Expr>,
+    max: Option<Expr>,
+    step: Option<Expr>,
+    slider: bool,
+    suffix: Option<LitStr>,
+    password: bool,
+    multiline: bool,
+    path: Option<LitStr>,
+    filter: Option<LitStr>,
+    list: Option<Vec<Choice>>,
+    color: bool,
+    color_alpha: bool,
+    skip: bool,
+    /// Every attribute given, to reject those which don't apply to the field.
+    given: Vec<(String, Span)>,
+}
+
+impl FieldAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("obs")) {
+            attr.parse_nested_meta(|meta| {
+                let path = &meta.path;
+                if let Some(ident) = path.get_ident() {
+                    out.given.push((ident.to_string(), ident.span()));
+                }
+                if path.is_ident("name") {
+                    out.name = Some(meta.value()?.parse()?);
+                } else if path.is_ident("description") {
+                    out.description = Some(meta.value()?.parse()?);
+                } else if path.is_ident("tooltip") {
+                    out.tooltip = Some(meta.value()?.parse()?);
+                } else if path.is_ident("default") {
+                    out.default = Some(meta.value()?.parse()?);
+                } else if path.is_ident("min") {
+                    out.min = Some(meta.value()?.parse()?);
+                } else if path.is_ident("max") {
+                    out.max = Some(meta.value()?.parse()?);
+                } else if path.is_ident("step") {
+                    out.step = Some(meta.value()?.parse()?);
+                } else if path.is_ident("slider") {
+                    out.slider = true;
+                } else if path.is_ident("suffix") {
+                    out.suffix = Some(meta.value()?.parse()?);
+                } else if path.is_ident("password") {
+                    out.password = true;
+                } else if path.is_ident("multiline") {
+                    out.multiline = true;
+                } else if path.is_ident("path") {
+                    out.path = Some(meta.value()?.parse()?);
+                } else if path.is_ident("filter") {
+                    out.filter = Some(meta.value()?.parse()?);
+                } else if path.is_ident("list") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let choices = Punctuated::<Choice, Token![,]>::parse_terminated(&content)?;
+                    out.list = Some(choices.into_iter().collect());
+                } else if path.is_ident("color") {
+                    out.color = true;
+                } else if path.is_ident("color_alpha") {
+                    out.color_alpha = true;
+                } else if path.is_ident("skip") {
+                    out.skip = true;
+                } else {
+                    return Err(meta.error("unknown `obs` attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+struct Setting {
+    ident: Ident,
+    ty: Type,
+    attrs: FieldAttrs,
+    kind: Kind,
+    key: LitStr,
+}
+
+/// Attributes which apply to every kind of field.
+const COMMON_ATTRS: &[&str] = &["name", "description", "tooltip", "default", "skip"];
+
+/// Parses an integer literal, possibly negative.
+fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        Expr::Paren(syn::ExprParen { expr, .. }) => int_literal(expr),
+        _ => None,
+    }
+}
+
+impl Setting {
+    /// Rejects attributes which would be ignored for the kind of the field,
+    /// and integer bounds which OBS can't store.
+    fn check_attrs(&self) -> syn::Result<()> {
+        let attrs = &self.attrs;
+        let number = &["min", "max", "step", "slider", "suffix"][..];
+        let (allowed, what) = match self.kind {
+            Kind::Bool => (&[][..], "a `bool` field"),
+            _ if attrs.list.is_some() => (&["list"][..], "a list"),
+            Kind::Int if attrs.color_alpha => (&["color_alpha"][..], "a color"),
+            Kind::Int if attrs.color => (&["color"][..], "a color"),
+            Kind::Int => (number, "an integer field"),
+            Kind::Float => (number, "a float field"),
+            Kind::Text if attrs.path.is_some() => (&["path", "filter"][..], "a path"),
+            Kind::Text if attrs.password => (&["password"][..], "a password field"),
+            Kind::Text => (&["multiline"][..], "a text field"),
+        };
+        for (name, span) in &attrs.given {
+            if !COMMON_ATTRS.contains(&name.as_str()) && !allowed.contains(&name.as_str()) {
+                return Err(syn::Error::new(
+                    *span,
+                    format!("`{}` can't be used on {}", name, what),
+                ));
+            }
+        }
+
+        if self.kind == Kind::Int {
+            let c_int = i32::MIN as i128..=i32::MAX as i128;
+            for value in [&attrs.min, &attrs.max, &attrs.step].into_iter().flatten() {
+                if int_literal(value).is_some_and(|value| !c_int.contains(&value)) {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "OBS stores integer ranges as `c_int`, this value doesn't fit",
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn default_value(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match &self.attrs.default {
+            Some(default) => quote!(#default),
+            // `ObsString` has no `Default`
+            None if self.kind == Kind::Text => quote!(<#ty>::from("")),
+            None => quote!(::core::default::Default::default()),
+        }
+    }
+
+    /// Bounds used for a missing `min` or `max` of an integer field. OBS
+    /// stores integer ranges as `c_int`, so wider types are clamped.
+    fn int_bounds(&self) -> (TokenStream2, TokenStream2) {
+        let ty = &self.ty;
+        let c_int = quote!(::std::os::raw::c_int);
+        let name = type_name(ty).unwrap_or_default();
+        let min = match name.as_str() {
+            "i64" | "isize" => quote!(#c_int::MIN as #ty),
+            _ => quote!(<#ty>::MIN),
+        };
+        let max = match name.as_str() {
+            "i64" | "isize" | "u32" | "u64" | "usize" => quote!(#c_int::MAX as #ty),
+            _ => quote!(<#ty>::MAX),
+        };
+        (min, max)
+    }
+
+    fn add_property(&self) -> syn::Result<TokenStream2> {
+        let Setting {
+            ty,
+            attrs,
+            kind,
+            key,
+            ..
+        } = self;
+        let description = attrs.description.as_ref().unwrap_or(key);
+        let name = quote!(::obs_wrapper::obs_string!(#key));
+        let description = quote!(::obs_wrapper::obs_string!(#description));
+        let props = quote!(::obs_wrapper::properties);
+        self.check_attrs()?;
+
+        let add = if let Some(choices) = &attrs.list {
+            let (list_ty, values) = match kind {
+                Kind::Int => (quote!(i64), quote!(as i64)),
+                Kind::Float => (quote!(f64), quote!(as f64)),
+                Kind::Text => (quote!(::obs_wrapper::string::ObsString), quote!(.into())),
+                Kind::Bool => {
+                    return Err(syn::Error::new(
+                        self.ident.span(),
+                        "a `bool` can't be a list",
+                    ));
+                }
+            };
+            let pushes = choices.iter().map(|Choice { label, value }| {
+                quote!(list.push(::obs_wrapper::obs_string!(#label), (#value) #values);)
+            });
+            quote! {
+                props
+                    .add_list::<#list_ty>(#name, #description, false)
+                    .map(|mut list| {
+                        #(#pushes)*
+                        list
+                    })
+            }
+        } else {
+            let prop = match kind {
+                Kind::Bool => quote!(#props::BoolProp),
+                Kind::Int if attrs.color_alpha => quote!(#props::ColorAlphaProp),
+                Kind::Int if attrs.color => quote!(#props::ColorProp),
+                Kind::Int | Kind::Float => {
+                    let mut prop = if *kind == Kind::Int {
+                        let step = attrs.step.iter();
+                        quote!(#props::NumberProp::<#ty>::new_int() #(.with_step(#step))*)
+                    } else {
+                        let step = match &attrs.step {
+                            Some(step) => quote!(#step),
+                            None => quote!(0.1),
+                        };
+                        quote!(#props::NumberProp::<#ty>::new_float(#step))
+                    };
+                    let given = |value: &Option<Expr>| value.as_ref().map(|value| quote!(#value));
+                    let (mut min, mut max) = (given(&attrs.min), given(&attrs.max));
+                    if *kind == Kind::Int {
+                        let (default_min, default_max) = self.int_bounds();
+                        min = min.or(Some(default_min));
+                        max = max.or(Some(default_max));
+                    }
+                    if min.is_some() || max.is_some() {
+                        let bound = |value: Option<TokenStream2>| match value {
+                            Some(value) => quote!(::core::ops::Bound::<#ty>::Included(#value)),
+                            None => quote!(::core::ops::Bound::<#ty>::Unbounded),
+                        };
+                        let (min, max) = (bound(min), bound(max));
+                        prop = quote!(#prop.with_range((#min, #max)));
+                    }
+                    if attrs.slider {
+                        prop = quote!(#prop.with_slider());
+                    }
+                    if let Some(suffix) = &attrs.suffix {
+                        prop = quote!(#prop.with_suffix(#suffix));
+                    }
+                    prop
+                }
+                Kind::Text => {
+                    if let Some(path) = &attrs.path {
+                        let typ = match path.value().as_str() {
+                            "file" => quote!(File),
+                            "file_save" => quote!(FileSave),
+                            "directory" => quote!(Directory),
+                            _ => {
+                                return Err(syn::Error::new(
+                                    path.span(),
+                                    "expected \"file\", \"file_save\" or \"directory\"",
+                                ));
+                            }
+                        };
+                        let filter = attrs.filter.iter();
+                        quote! {
+                            #props::PathProp::new(#props::PathType::#typ)
+                                #(.with_filter(::obs_wrapper::obs_string!(#filter)))*
+                        }
+                    } else {
+                        let typ = if attrs.password {
+                            quote!(Password)
+                        } else if attrs.multiline {
+                            quote!(Multiline)
+                        } else {
+                            quote!(Default)
+                        };
+                        quote!(#props::TextProp::new(#props::TextType::#typ))
+                    }
+                }
+            };
+            quote!(props.add(#name, #description, #prop))
+        };
+
+        // `None` can't happen, keys are checked to be unique.
+        match &attrs.tooltip {
+            Some(tooltip) => Ok(quote! {
+                if let ::core::option::Option::Some(mut prop) = #add {
+                    prop.set_long_description(::obs_wrapper::obs_string!(#tooltip));
+                }
+            }),
+            None => Ok(quote!(#add;)),
+        }
+    }
+}
+
+fn expand_obs_settings(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`ObsSettings` can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "`ObsSettings` needs a struct with named fields",
+        ));
+    };
+
+    let mut settings = Vec::new();
+    let mut skipped = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.clone().unwrap();
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        if attrs.skip {
+            skipped.push(ident);
+            continue;
+        }
+        let kind = Kind::of(&field.ty).ok_or_else(|| {
+            syn::Error::new(
+                field.ty.span(),
+                "unsupported settings type, mark the field with `#[obs(skip)]`",
+            )
+        })?;
+        let key = attrs
+            .name
+            .clone()
+            .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+        if settings
+            .iter()
+            .any(|setting: &Setting| setting.key.value() == key.value())
+        {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("duplicate settings key `{}`", key.value()),
+            ));
+        }
+        settings.push(Setting {
+            ident,
+            ty: field.ty.clone(),
+            attrs,
+            kind,
+            key,
+        });
+    }
+
+    let properties = settings
+        .iter()
+        .map(Setting::add_property)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let defaults = settings.iter().map(|setting| {
+        let Setting { ty, key, .. } = setting;
+        let default = setting.default_value();
+        quote! {
+            let value: #ty = #default;
+            settings.set_default::<#ty>(::obs_wrapper::obs_string!(#key), value)?;
+        }
+    });
+    let fields = settings.iter().map(|setting| {
+        let Setting { ident, ty, key, .. } = setting;
+        let default = setting.default_value();
+        quote! {
+            #ident: settings
+                .get::<#ty>(::obs_wrapper::obs_string!(#key))
+                .unwrap_or_else(|| #default),
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::obs_wrapper::settings::ObsSettings for #name #ty_generics #where_clause {
+            fn properties() -> ::obs_wrapper::properties::Properties {
+                let mut props = ::obs_wrapper::properties::Properties::new();
+                #(#properties)*
+                props
+            }
+
+            fn set_defaults(settings: &mut ::obs_wrapper::data::DataObj) -> ::obs_wrapper::Result<()> {
+                #(#defaults)*
+                ::core::result::Result::Ok(())
+            }
+
+            fn from_settings(settings: &::obs_wrapper::data::DataObj) -> Self {
+                Self {
+                    #(#fields)*
+                    #(#skipped: ::core::default::Default::default(),)*
+                }
+            }
+        }
+    })
+}
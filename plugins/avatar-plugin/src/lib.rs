@@ -1,4 +1,6 @@
-use obs_wrapper::{obs_register_module, obs_string, obs_sys, prelude::*, properties::*, source::*};
+use obs_wrapper::{
+    obs_register_module, obs_string, obs_sys, prelude::*, properties::*, source::*, source_info,
+};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -116,14 +118,12 @@ struct AvatarSource {
 }
 
 impl Sourceable for AvatarSource {
+    const TYPE: SourceType = SourceType::Input;
+
     fn get_id() -> ObsString {
         obs_string!("avatar_source")
     }
 
-    fn get_type() -> SourceType {
-        SourceType::Input
-    }
-
     fn create(create: &mut CreatableSourceContext<Self>, source: SourceRef) -> Self {
         let settings = &create.settings;
 
@@ -659,7 +659,7 @@ impl MouseMoveSource for AvatarSource {
 
 // impl FilterAudioSource для обработки аудио входа
 // Если вы хотите, чтобы это был фильтр, а не источник
-// Раскомментируйте этот блок и измените get_type() на SourceType::Filter
+// Раскомментируйте этот блок и измените TYPE на SourceType::Filter
 
 /*
 impl FilterAudioSource for AvatarSource {
@@ -692,21 +692,7 @@ impl Module for AvatarModule {
     }
 
     fn load(&mut self, load_context: &mut LoadContext) -> bool {
-        let source = load_context
-            .create_source_builder::<AvatarSource>()
-            .enable_get_name()
-            .enable_get_width()
-            .enable_get_height()
-            .enable_get_properties()
-            .enable_update()
-            .enable_video_tick()
-            .enable_video_render()
-            .enable_key_click()
-            .enable_mouse_click()
-            .enable_mouse_move()
-            // TODO: Uncomment when FilterAudioSource is implemented
-            // .enable_filter_audio()
-            .build();
+        let source = source_info!(load_context, AvatarSource, Input);
 
         load_context.register_source(source);
 
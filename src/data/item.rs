@@ -0,0 +1,142 @@
+use std::{borrow::Cow, ffi::CStr, marker::PhantomData};
+
+use obs_sys::{
+    obs_data_first, obs_data_item_byname, obs_data_item_get_name,
+    obs_data_item_has_autoselect_value, obs_data_item_has_default_value,
+    obs_data_item_has_user_value, obs_data_item_next, obs_data_item_release, obs_data_item_t,
+};
+
+use super::{DataObj, DataType, FromDataItem};
+use crate::{wrapper::PtrWrapper, Result};
+
+/// A smart pointer to a single `obs_data_item_t` of a [`DataObj`].
+pub struct DataItem<'parent> {
+    raw: *mut obs_data_item_t,
+    _parent: PhantomData<&'parent DataObj<'parent>>,
+}
+
+impl crate::wrapper::PtrWrapperInternal for DataItem<'_> {
+    unsafe fn new_internal(ptr: *mut Self::Pointer) -> Self {
+        Self {
+            raw: ptr,
+            _parent: PhantomData,
+        }
+    }
+
+    unsafe fn get_internal(&self) -> *mut Self::Pointer {
+        self.raw
+    }
+}
+
+unsafe fn release_item(mut ptr: *mut obs_data_item_t) {
+    unsafe { obs_data_item_release(&mut ptr) }
+}
+
+impl_ptr_wrapper!(DataItem<'_>, obs_data_item_t, @identity, release_item);
+
+impl DataItem<'_> {
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { CStr::from_ptr(obs_data_item_get_name(self.raw)) }.to_string_lossy()
+    }
+
+    /// Returns the type of this item, or an error if the item has a type
+    /// this crate doesn't know about.
+    pub fn typ(&self) -> Result<DataType> {
+        unsafe { DataType::from_item(self.raw) }
+    }
+
+    fn get_with<T: FromDataItem>(
+        &self,
+        read: unsafe fn(*mut obs_data_item_t) -> Option<T>,
+    ) -> Option<T> {
+        if self.typ().ok()? == T::typ() {
+            unsafe { read(self.raw) }
+        } else {
+            None
+        }
+    }
+
+    /// Reads the value of this item. Returns the user value, or the default
+    /// value if no user value is set.
+    pub fn get<T: FromDataItem>(&self) -> Option<T> {
+        self.get_with(T::from_item_unchecked)
+    }
+
+    /// Reads the default value of this item. If it has none, numbers read as
+    /// `0` and strings as `""`.
+    pub fn get_default<T: FromDataItem>(&self) -> Option<T> {
+        self.get_with(T::from_item_default_unchecked)
+    }
+
+    /// Reads the autoselect value of this item. If it has none, numbers read
+    /// as `0` and strings as `""`.
+    pub fn get_autoselect<T: FromDataItem>(&self) -> Option<T> {
+        self.get_with(T::from_item_autoselect_unchecked)
+    }
+
+    /// Whether this item has a user value.
+    pub fn has_user_value(&self) -> bool {
+        unsafe { obs_data_item_has_user_value(self.raw) }
+    }
+
+    /// Whether this item has a default value.
+    pub fn has_default_value(&self) -> bool {
+        unsafe { obs_data_item_has_default_value(self.raw) }
+    }
+
+    /// Whether this item has an autoselect value.
+    pub fn has_autoselect_value(&self) -> bool {
+        unsafe { obs_data_item_has_autoselect_value(self.raw) }
+    }
+}
+
+/// Iterator over the items of a [`DataObj`], including items which only have
+/// a default value.
+pub struct DataObjIter<'a> {
+    obj: &'a DataObj<'a>,
+    item: *mut obs_data_item_t,
+}
+
+impl<'a> DataObjIter<'a> {
+    pub(super) fn new(obj: &'a DataObj<'a>) -> Self {
+        Self {
+            obj,
+            item: unsafe { obs_data_first(obj.raw) },
+        }
+    }
+}
+
+impl<'a> Iterator for DataObjIter<'a> {
+    type Item = DataItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.item.is_null() {
+            return None;
+        }
+        unsafe {
+            // `obs_data_item_next` releases the current item, so take another
+            // reference for the caller before moving on.
+            let name = obs_data_item_get_name(self.item);
+            let current = obs_data_item_byname(self.obj.raw, name);
+            obs_data_item_next(&mut self.item);
+            DataItem::from_raw_unchecked(current)
+        }
+    }
+}
+
+impl Drop for DataObjIter<'_> {
+    fn drop(&mut self) {
+        if !self.item.is_null() {
+            unsafe { obs_data_item_release(&mut self.item) }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DataObj<'_> {
+    type Item = DataItem<'a>;
+    type IntoIter = DataObjIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
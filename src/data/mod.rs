@@ -0,0 +1,861 @@
+#![allow(non_upper_case_globals)]
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    os::raw::c_char,
+};
+
+use obs_sys::{
+    obs_data_array_count, obs_data_array_create, obs_data_array_erase, obs_data_array_insert,
+    obs_data_array_item, obs_data_array_push_back, obs_data_array_release, obs_data_array_t,
+    obs_data_clear, obs_data_create, obs_data_create_from_json, obs_data_create_from_json_file,
+    obs_data_create_from_json_file_safe, obs_data_erase, obs_data_get_json,
+    obs_data_get_json_pretty, obs_data_has_autoselect_value, obs_data_has_default_value,
+    obs_data_has_user_value, obs_data_item_byname, obs_data_item_get_array,
+    obs_data_item_get_autoselect_array, obs_data_item_get_autoselect_bool,
+    obs_data_item_get_autoselect_double, obs_data_item_get_autoselect_int,
+    obs_data_item_get_autoselect_obj, obs_data_item_get_autoselect_string, obs_data_item_get_bool,
+    obs_data_item_get_default_array, obs_data_item_get_default_bool,
+    obs_data_item_get_default_double, obs_data_item_get_default_int, obs_data_item_get_default_obj,
+    obs_data_item_get_default_string, obs_data_item_get_double, obs_data_item_get_int,
+    obs_data_item_get_obj, obs_data_item_get_string, obs_data_item_gettype, obs_data_item_numtype,
+    obs_data_item_release, obs_data_item_t, obs_data_number_type,
+    obs_data_number_type_OBS_DATA_NUM_DOUBLE, obs_data_number_type_OBS_DATA_NUM_INT,
+    obs_data_release, obs_data_save_json, obs_data_save_json_pretty_safe, obs_data_save_json_safe,
+    obs_data_set_array, obs_data_set_autoselect_bool, obs_data_set_autoselect_double,
+    obs_data_set_autoselect_int, obs_data_set_autoselect_obj, obs_data_set_autoselect_string,
+    obs_data_set_bool, obs_data_set_default_array, obs_data_set_default_bool,
+    obs_data_set_default_double, obs_data_set_default_int, obs_data_set_default_obj,
+    obs_data_set_default_string, obs_data_set_double, obs_data_set_int, obs_data_set_obj,
+    obs_data_set_string, obs_data_t, obs_data_type, obs_data_type_OBS_DATA_ARRAY,
+    obs_data_type_OBS_DATA_BOOLEAN, obs_data_type_OBS_DATA_NUMBER, obs_data_type_OBS_DATA_OBJECT,
+    obs_data_type_OBS_DATA_STRING, obs_data_unset_autoselect_value, obs_data_unset_default_value,
+    obs_data_unset_user_value, size_t,
+};
+
+use crate::{
+    string::{ObsString, TryIntoObsString},
+    wrapper::PtrWrapper,
+    Error, Result,
+};
+
+mod item;
+#[cfg(feature = "serde")]
+mod serde;
+
+pub use item::{DataItem, DataObjIter};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum DataType {
+    String,
+    Int,
+    Double,
+    Boolean,
+    /// Map container
+    Object,
+    /// Array container
+    Array,
+}
+
+impl DataType {
+    pub fn new(typ: obs_data_type, numtyp: obs_data_number_type) -> Result<Self> {
+        match typ {
+            obs_data_type_OBS_DATA_STRING => Ok(Self::String),
+            obs_data_type_OBS_DATA_NUMBER => match numtyp {
+                obs_data_number_type_OBS_DATA_NUM_INT => Ok(Self::Int),
+                obs_data_number_type_OBS_DATA_NUM_DOUBLE => Ok(Self::Double),
+                _ => Err(Error::UnknownNumberType(numtyp as i64)),
+            },
+            obs_data_type_OBS_DATA_BOOLEAN => Ok(Self::Boolean),
+            obs_data_type_OBS_DATA_OBJECT => Ok(Self::Object),
+            obs_data_type_OBS_DATA_ARRAY => Ok(Self::Array),
+            _ => Err(Error::UnknownDataType(typ as i64)),
+        }
+    }
+
+    unsafe fn from_item(item_ptr: *mut obs_data_item_t) -> Result<Self> {
+        let typ = unsafe { obs_data_item_gettype(item_ptr) };
+        let numtyp = unsafe { obs_data_item_numtype(item_ptr) };
+        Self::new(typ, numtyp)
+    }
+}
+
+/// Types which can be read from a `obs_data_item_t`.
+pub trait FromDataItem: Sized {
+    fn typ() -> DataType;
+    /// Reads the current value of the item, which falls back to the default
+    /// value if no user value is set.
+    ///
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self>;
+
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self>;
+
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self>;
+}
+
+/// Types which can be written into a `obs_data_t`, the counterpart of
+/// [`FromDataItem`].
+pub trait ToDataItem: Sized {
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()>;
+
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn set_default_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self)
+        -> Result<()>;
+
+    /// # Safety
+    ///
+    /// Pointer must be valid.
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()>;
+}
+
+unsafe fn cow_from_ptr<'a>(ptr: *const std::os::raw::c_char) -> Option<Cow<'a, str>> {
+    if ptr.is_null() {
+        return None;
+    }
+    Some(unsafe { CStr::from_ptr(ptr) }.to_string_lossy())
+}
+
+impl FromDataItem for Cow<'_, str> {
+    fn typ() -> DataType {
+        DataType::String
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { cow_from_ptr(obs_data_item_get_string(item)) }
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { cow_from_ptr(obs_data_item_get_default_string(item)) }
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { cow_from_ptr(obs_data_item_get_autoselect_string(item)) }
+    }
+}
+
+impl ToDataItem for Cow<'_, str> {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        let s = CString::new(val.as_ref())?;
+        unsafe { obs_data_set_string(obj, name.as_ptr(), s.as_ptr()) };
+        Ok(())
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        let s = CString::new(val.as_ref())?;
+        unsafe { obs_data_set_default_string(obj, name.as_ptr(), s.as_ptr()) };
+        Ok(())
+    }
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        let s = CString::new(val.as_ref())?;
+        unsafe { obs_data_set_autoselect_string(obj, name.as_ptr(), s.as_ptr()) };
+        Ok(())
+    }
+}
+
+impl FromDataItem for String {
+    fn typ() -> DataType {
+        DataType::String
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Cow::from_item_unchecked(item) }.map(Cow::into_owned)
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Cow::from_item_default_unchecked(item) }.map(Cow::into_owned)
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Cow::from_item_autoselect_unchecked(item) }.map(Cow::into_owned)
+    }
+}
+
+impl ToDataItem for String {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        unsafe { Cow::<str>::set_unchecked(obj, name, val.into()) }
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { Cow::<str>::set_default_unchecked(obj, name, val.into()) }
+    }
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { Cow::<str>::set_autoselect_unchecked(obj, name, val.into()) }
+    }
+}
+
+impl FromDataItem for ObsString {
+    fn typ() -> DataType {
+        DataType::String
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        let ptr = unsafe { obs_data_item_get_string(item) };
+        ptr.try_into_obs_string().ok()
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        let ptr = unsafe { obs_data_item_get_default_string(item) };
+        ptr.try_into_obs_string().ok()
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        let ptr = unsafe { obs_data_item_get_autoselect_string(item) };
+        ptr.try_into_obs_string().ok()
+    }
+}
+
+impl ToDataItem for ObsString {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        unsafe { obs_data_set_string(obj, name.as_ptr(), val.as_ptr()) };
+        Ok(())
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_default_string(obj, name.as_ptr(), val.as_ptr()) };
+        Ok(())
+    }
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_autoselect_string(obj, name.as_ptr(), val.as_ptr()) };
+        Ok(())
+    }
+}
+
+macro_rules! impl_get_int {
+    ($($t:ty)*) => {
+        $(
+            impl FromDataItem for $t {
+                fn typ() -> DataType {
+                    DataType::Int
+                }
+                unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_int(item) } as $t)
+                }
+                unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_default_int(item) } as $t)
+                }
+                unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_autoselect_int(item) } as $t)
+                }
+            }
+
+            impl ToDataItem for $t {
+                unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_int(obj, name.as_ptr(), val as i64) };
+                    Ok(())
+                }
+                unsafe fn set_default_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_default_int(obj, name.as_ptr(), val as i64) };
+                    Ok(())
+                }
+                unsafe fn set_autoselect_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_autoselect_int(obj, name.as_ptr(), val as i64) };
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_get_int!(i64 u64 i32 u32 i16 u16 i8 u8 isize usize);
+
+macro_rules! impl_get_float {
+    ($($t:ty)*) => {
+        $(
+            impl FromDataItem for $t {
+                fn typ() -> DataType {
+                    DataType::Double
+                }
+                unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_double(item) } as $t)
+                }
+                unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_default_double(item) } as $t)
+                }
+                unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+                    Some(unsafe { obs_data_item_get_autoselect_double(item) } as $t)
+                }
+            }
+
+            impl ToDataItem for $t {
+                unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_double(obj, name.as_ptr(), val as f64) };
+                    Ok(())
+                }
+                unsafe fn set_default_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_default_double(obj, name.as_ptr(), val as f64) };
+                    Ok(())
+                }
+                unsafe fn set_autoselect_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+                    unsafe { obs_data_set_autoselect_double(obj, name.as_ptr(), val as f64) };
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_get_float!(f64 f32);
+
+impl FromDataItem for bool {
+    fn typ() -> DataType {
+        DataType::Boolean
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        Some(unsafe { obs_data_item_get_bool(item) })
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        Some(unsafe { obs_data_item_get_default_bool(item) })
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        Some(unsafe { obs_data_item_get_autoselect_bool(item) })
+    }
+}
+
+impl ToDataItem for bool {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        unsafe { obs_data_set_bool(obj, name.as_ptr(), val) };
+        Ok(())
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_default_bool(obj, name.as_ptr(), val) };
+        Ok(())
+    }
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_autoselect_bool(obj, name.as_ptr(), val) };
+        Ok(())
+    }
+}
+
+// https://github.com/obsproject/obs-studio/blob/01610d8c06edb08d0cc3155cb91b3e52e9a6473e/libobs/obs-data.c#L1798
+// `os_atomic_inc_long(&obj->ref);`
+impl FromDataItem for DataObj<'_> {
+    fn typ() -> DataType {
+        DataType::Object
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_obj(item)) }
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_default_obj(item)) }
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_autoselect_obj(item)) }
+    }
+}
+
+// `obs_data_set_*_obj` adds its own reference, ours is released when `val` is
+// dropped.
+impl ToDataItem for DataObj<'_> {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        unsafe { obs_data_set_obj(obj, name.as_ptr(), val.as_ptr_mut()) };
+        Ok(())
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_default_obj(obj, name.as_ptr(), val.as_ptr_mut()) };
+        Ok(())
+    }
+    unsafe fn set_autoselect_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_autoselect_obj(obj, name.as_ptr(), val.as_ptr_mut()) };
+        Ok(())
+    }
+}
+
+// https://github.com/obsproject/obs-studio/blob/01610d8c06edb08d0cc3155cb91b3e52e9a6473e/libobs/obs-data.c#L1811
+// `os_atomic_inc_long(&array->ref);`
+impl FromDataItem for DataArray<'_> {
+    fn typ() -> DataType {
+        DataType::Array
+    }
+    unsafe fn from_item_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_array(item)) }
+    }
+    unsafe fn from_item_default_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_default_array(item)) }
+    }
+    unsafe fn from_item_autoselect_unchecked(item: *mut obs_data_item_t) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_data_item_get_autoselect_array(item)) }
+    }
+}
+
+impl ToDataItem for DataArray<'_> {
+    unsafe fn set_unchecked(obj: *mut obs_data_t, name: ObsString, val: Self) -> Result<()> {
+        unsafe { obs_data_set_array(obj, name.as_ptr(), val.as_ptr_mut()) };
+        Ok(())
+    }
+    unsafe fn set_default_unchecked(
+        obj: *mut obs_data_t,
+        name: ObsString,
+        val: Self,
+    ) -> Result<()> {
+        unsafe { obs_data_set_default_array(obj, name.as_ptr(), val.as_ptr_mut()) };
+        Ok(())
+    }
+    unsafe fn set_autoselect_unchecked(
+        _obj: *mut obs_data_t,
+        _name: ObsString,
+        _val: Self,
+    ) -> Result<()> {
+        // `obs_data_set_autoselect_array` doesn't exist
+        Err(Error::Unsupported("autoselect value for an array"))
+    }
+}
+
+/// A smart pointer to `obs_data_t`
+pub struct DataObj<'parent> {
+    raw: *mut obs_data_t,
+    _parent: PhantomData<&'parent DataObj<'parent>>,
+}
+
+impl crate::wrapper::PtrWrapperInternal for DataObj<'_> {
+    unsafe fn new_internal(ptr: *mut Self::Pointer) -> Self {
+        Self {
+            raw: ptr,
+            _parent: PhantomData,
+        }
+    }
+
+    unsafe fn get_internal(&self) -> *mut Self::Pointer {
+        self.raw
+    }
+}
+
+// impl_ptr_wrapper!(DataObj, obs_data_t, @addref: obs_data_addref, obs_data_release);
+impl_ptr_wrapper!(DataObj<'_>, obs_data_t, @identity, obs_data_release);
+
+impl Default for DataObj<'_> {
+    fn default() -> Self {
+        DataObj::new()
+    }
+}
+
+impl DataObj<'_> {
+    /// Creates a empty data object
+    pub fn new() -> Self {
+        unsafe {
+            let raw = obs_data_create();
+            Self::from_raw_unchecked(raw).expect("obs_data_create")
+        }
+    }
+
+    /// Loads data into a object from a JSON string.
+    pub fn from_json(json_str: impl Into<ObsString>) -> Option<Self> {
+        let json_str = json_str.into();
+        unsafe {
+            let raw = obs_data_create_from_json(json_str.as_ptr());
+            Self::from_raw_unchecked(raw)
+        }
+    }
+
+    /// Loads data into a object from a JSON file.
+    /// * `backup_ext`: optional backup file path in case the original file is
+    ///   bad.
+    pub fn from_json_file(
+        json_file: impl Into<ObsString>,
+        backup_ext: impl Into<Option<ObsString>>,
+    ) -> Option<Self> {
+        let json_file = json_file.into();
+
+        unsafe {
+            let raw = if let Some(backup_ext) = backup_ext.into() {
+                obs_data_create_from_json_file_safe(json_file.as_ptr(), backup_ext.as_ptr())
+            } else {
+                obs_data_create_from_json_file(json_file.as_ptr())
+            };
+            Self::from_raw_unchecked(raw)
+        }
+    }
+
+    /// Looks up the item for `name` and reads it with `read` if it is of the
+    /// expected type.
+    fn get_with<T: FromDataItem>(
+        &self,
+        name: impl Into<ObsString>,
+        read: unsafe fn(*mut obs_data_item_t) -> Option<T>,
+    ) -> Option<T> {
+        let name = name.into();
+        let mut item_ptr = unsafe { obs_data_item_byname(self.as_ptr() as *mut _, name.as_ptr()) };
+        if item_ptr.is_null() {
+            return None;
+        }
+        // Release it immediately since it is also referenced by this object.
+        unsafe {
+            obs_data_item_release(&mut item_ptr);
+        }
+        assert!(!item_ptr.is_null()); // We should not be the last holder
+
+        let typ = unsafe { DataType::from_item(item_ptr) };
+
+        if typ.ok()? == T::typ() {
+            unsafe { read(item_ptr) }
+        } else {
+            None
+        }
+    }
+
+    /// Fetches a property from this object. Numbers are implicitly casted.
+    ///
+    /// Returns the user value, or the default value if no user value is set.
+    pub fn get<T: FromDataItem>(&self, name: impl Into<ObsString>) -> Option<T> {
+        self.get_with(name, T::from_item_unchecked)
+    }
+
+    /// Fetches the default value of a property from this object. If the
+    /// key has no default value, numbers read as `0` and strings as `""`.
+    pub fn get_default<T: FromDataItem>(&self, name: impl Into<ObsString>) -> Option<T> {
+        self.get_with(name, T::from_item_default_unchecked)
+    }
+
+    /// Fetches the autoselect value of a property from this object. If the
+    /// key has no autoselect value, numbers read as `0` and strings as `""`.
+    pub fn get_autoselect<T: FromDataItem>(&self, name: impl Into<ObsString>) -> Option<T> {
+        self.get_with(name, T::from_item_autoselect_unchecked)
+    }
+
+    /// Sets the user value for the key.
+    ///
+    /// Fails if a string value contains a nul byte.
+    pub fn set<T: ToDataItem>(
+        &mut self,
+        name: impl Into<ObsString>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        unsafe { T::set_unchecked(self.as_ptr_mut(), name.into(), value.into()) }
+    }
+
+    /// Sets a default value for the key.
+    ///
+    /// Fails if a string value contains a nul byte.
+    pub fn set_default<T: ToDataItem>(
+        &mut self,
+        name: impl Into<ObsString>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        unsafe { T::set_default_unchecked(self.as_ptr_mut(), name.into(), value.into()) }
+    }
+
+    /// Sets an autoselect value for the key, which is shown by the UI instead
+    /// of the user value when it is set.
+    ///
+    /// Notes
+    /// -----
+    /// Setting an autoselect value for a [`DataArray`] is not supported and
+    /// returns [`Error::Unsupported`].
+    pub fn set_autoselect<T: ToDataItem>(
+        &mut self,
+        name: impl Into<ObsString>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        unsafe { T::set_autoselect_unchecked(self.as_ptr_mut(), name.into(), value.into()) }
+    }
+
+    /// Removes the user value of the key, so that the default value is used.
+    pub fn unset_user_value(&mut self, name: impl Into<ObsString>) {
+        let name = name.into();
+        unsafe { obs_data_unset_user_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Removes the default value of the key.
+    pub fn unset_default_value(&mut self, name: impl Into<ObsString>) {
+        let name = name.into();
+        unsafe { obs_data_unset_default_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Removes the autoselect value of the key.
+    pub fn unset_autoselect_value(&mut self, name: impl Into<ObsString>) {
+        let name = name.into();
+        unsafe { obs_data_unset_autoselect_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Creates a JSON representation of this object.
+    pub fn get_json(&self) -> Option<String> {
+        unsafe {
+            let ptr = obs_data_get_json(self.raw);
+            Some(ptr.try_into_obs_string().ok()?.as_str().to_string())
+        }
+    }
+
+    /// Fetches the item for the key.
+    pub fn item(&self, name: impl Into<ObsString>) -> Option<DataItem<'_>> {
+        let name = name.into();
+        unsafe { DataItem::from_raw_unchecked(obs_data_item_byname(self.raw, name.as_ptr())) }
+    }
+
+    /// Iterates over all items of this object.
+    pub fn iter(&self) -> DataObjIter<'_> {
+        DataObjIter::new(self)
+    }
+
+    /// Whether the key has a user value, `false` if it doesn't exist.
+    pub fn has_user_value(&self, name: impl Into<ObsString>) -> bool {
+        let name = name.into();
+        unsafe { obs_data_has_user_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Whether the key has a default value, `false` if it doesn't exist.
+    pub fn has_default_value(&self, name: impl Into<ObsString>) -> bool {
+        let name = name.into();
+        unsafe { obs_data_has_default_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Whether the key has an autoselect value, `false` if it doesn't exist.
+    pub fn has_autoselect_value(&self, name: impl Into<ObsString>) -> bool {
+        let name = name.into();
+        unsafe { obs_data_has_autoselect_value(self.raw, name.as_ptr()) }
+    }
+
+    /// Creates a pretty-printed JSON representation of this object.
+    pub fn get_json_pretty(&self) -> Option<String> {
+        unsafe {
+            let ptr = obs_data_get_json_pretty(self.raw);
+            Some(ptr.try_into_obs_string().ok()?.as_str().to_string())
+        }
+    }
+
+    /// Saves this object to a JSON file.
+    pub fn save_json(&self, file: impl Into<ObsString>) -> Result<()> {
+        let file = file.into();
+        if unsafe { obs_data_save_json(self.raw, file.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(Error::SaveJson(file.as_str().to_string()))
+        }
+    }
+
+    /// Saves this object to a JSON file without risking corrupting the
+    /// existing one. The data is first written to a file with `temp_ext`,
+    /// then the existing file is kept with `backup_ext` before the temporary
+    /// file replaces it.
+    /// * `backup_ext`: can be passed to [`DataObj::from_json_file`] to load
+    ///   the backup if the file turns out to be bad.
+    pub fn save_json_safe(
+        &self,
+        file: impl Into<ObsString>,
+        temp_ext: impl Into<ObsString>,
+        backup_ext: impl Into<ObsString>,
+    ) -> Result<()> {
+        self.save_json_safe_with(obs_data_save_json_safe, file, temp_ext, backup_ext)
+    }
+
+    /// Same as [`DataObj::save_json_safe`], but writes pretty-printed JSON.
+    pub fn save_json_pretty_safe(
+        &self,
+        file: impl Into<ObsString>,
+        temp_ext: impl Into<ObsString>,
+        backup_ext: impl Into<ObsString>,
+    ) -> Result<()> {
+        self.save_json_safe_with(obs_data_save_json_pretty_safe, file, temp_ext, backup_ext)
+    }
+
+    fn save_json_safe_with(
+        &self,
+        save: unsafe extern "C" fn(
+            *mut obs_data_t,
+            *const c_char,
+            *const c_char,
+            *const c_char,
+        ) -> bool,
+        file: impl Into<ObsString>,
+        temp_ext: impl Into<ObsString>,
+        backup_ext: impl Into<ObsString>,
+    ) -> Result<()> {
+        let file = file.into();
+        let temp_ext = temp_ext.into();
+        let backup_ext = backup_ext.into();
+        if unsafe {
+            save(
+                self.raw,
+                file.as_ptr(),
+                temp_ext.as_ptr(),
+                backup_ext.as_ptr(),
+            )
+        } {
+            Ok(())
+        } else {
+            Err(Error::SaveJson(file.as_str().to_string()))
+        }
+    }
+
+    /// Clears all values.
+    pub fn clear(&mut self) {
+        unsafe {
+            obs_data_clear(self.raw);
+        }
+    }
+
+    pub fn remove(&mut self, name: impl Into<ObsString>) {
+        let name = name.into();
+        unsafe {
+            obs_data_erase(self.raw, name.as_ptr());
+        }
+    }
+}
+
+pub struct DataArray<'parent> {
+    raw: *mut obs_data_array_t,
+    _parent: PhantomData<&'parent DataArray<'parent>>,
+}
+
+impl crate::wrapper::PtrWrapperInternal for DataArray<'_> {
+    unsafe fn new_internal(raw: *mut Self::Pointer) -> Self {
+        Self {
+            raw,
+            _parent: PhantomData,
+        }
+    }
+
+    unsafe fn get_internal(&self) -> *mut Self::Pointer {
+        self.raw
+    }
+}
+
+impl_ptr_wrapper!(DataArray<'_>, obs_data_array_t, @identity, obs_data_array_release);
+
+impl Default for DataArray<'_> {
+    fn default() -> Self {
+        DataArray::new()
+    }
+}
+
+impl DataArray<'_> {
+    /// Creates a new empty array.
+    pub fn new() -> Self {
+        unsafe {
+            let raw = obs_data_array_create();
+            Self::from_raw_unchecked(raw).expect("obs_data_array_create")
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { obs_data_array_count(self.raw) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<DataObj> {
+        // https://github.com/obsproject/obs-studio/blob/01610d8c06edb08d0cc3155cb91b3e52e9a6473e/libobs/obs-data.c#L1395
+        // os_atomic_inc_long(&data->ref);
+        let ptr = unsafe { obs_data_array_item(self.raw, index as size_t) };
+        unsafe { DataObj::from_raw_unchecked(ptr) }
+    }
+
+    /// Appends an object to the end of the array and returns its index. The
+    /// array holds its own reference to the object.
+    pub fn push(&mut self, obj: &DataObj) -> usize {
+        unsafe { obs_data_array_push_back(self.raw, obj.as_ptr_mut()) }
+    }
+
+    /// Inserts an object at `index`, shifting all objects after it. Returns
+    /// `false` if `index` is out of bounds, which is `index > len`.
+    pub fn insert(&mut self, index: usize, obj: &DataObj) -> bool {
+        if index > self.len() {
+            return false;
+        }
+        unsafe { obs_data_array_insert(self.raw, index, obj.as_ptr_mut()) }
+        true
+    }
+
+    /// Removes the object at `index`, shifting all objects after it, and
+    /// returns it. Returns `None` if `index` is out of bounds, which is
+    /// `index >= len`.
+    pub fn remove(&mut self, index: usize) -> Option<DataObj<'static>> {
+        if index >= self.len() {
+            return None;
+        }
+        let obj = unsafe { DataObj::from_raw_unchecked(obs_data_array_item(self.raw, index)) };
+        unsafe { obs_data_array_erase(self.raw, index) }
+        obj
+    }
+
+    pub fn iter(&self) -> DataArrayIter<'_> {
+        DataArrayIter {
+            array: self,
+            index: 0,
+            len: self.len(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a DataArray<'_> {
+    type Item = DataObj<'a>;
+    type IntoIter = DataArrayIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the objects of a [`DataArray`].
+pub struct DataArrayIter<'a> {
+    array: &'a DataArray<'a>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a> Iterator for DataArrayIter<'a> {
+    type Item = DataObj<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        let obj = self.array.get(self.index);
+        self.index += 1;
+        obj
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for DataArrayIter<'_> {}
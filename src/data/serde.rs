@@ -0,0 +1,1052 @@
+//! Serde support for [`DataObj`], enabled with the `serde` feature.
+//!
+//! Structs and maps are stored as objects, sequences as [`DataArray`]s. OBS
+//! arrays can only hold objects, so sequences of primitives are rejected.
+//! Unit enum variants are stored as strings, other variants as an object
+//! with a single key, like serde's default externally tagged representation.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Settings {
+//!     path: String,
+//!     width: u32,
+//!     mode: Option<Mode>,
+//! }
+//!
+//! let settings: Settings = data.deserialize()?;
+//! data.serialize(&settings)?;
+//! ```
+
+use serde::{
+    de::{self, value::MapAccessDeserializer, DeserializeOwned, IntoDeserializer},
+    ser::{self, Impossible, Serialize},
+};
+
+use super::{DataArray, DataObj, DataType};
+use crate::{
+    result::{Error, Result},
+    string::{ObsString, TryIntoObsString},
+};
+
+impl DataObj<'_> {
+    /// Creates a new object holding the fields of `value`.
+    pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self> {
+        let mut obj = DataObj::new();
+        obj.serialize(value)?;
+        Ok(obj)
+    }
+
+    /// Writes the fields of `value` into this object. Fields set to `None`
+    /// have their user value removed.
+    pub fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        value.serialize(ObjSerializer { obj: self })
+    }
+
+    /// Reads this object into `T`. Keys which are not present are treated as
+    /// missing fields, values of the wrong type are reported with
+    /// [`Error::DataTypeMismatch`].
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(ObjDeserializer {
+            obj: self,
+            path: String::new(),
+        })
+    }
+}
+
+fn item_type(obj: &DataObj, name: &str) -> Result<Option<DataType>> {
+    let name = name.try_into_obs_string()?;
+    obj.item(name).map(|item| item.typ()).transpose()
+}
+
+fn item_names(obj: &DataObj) -> Vec<String> {
+    obj.iter().map(|item| item.name().into_owned()).collect()
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+struct ObjDeserializer<'a, 'p> {
+    obj: &'a DataObj<'p>,
+    path: String,
+}
+
+impl ObjDeserializer<'_, '_> {
+    fn map_access(&self, names: Vec<String>) -> ObjMapAccess<'_, '_> {
+        ObjMapAccess {
+            obj: self.obj,
+            path: &self.path,
+            names: names.into_iter(),
+            current: None,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ObjDeserializer<'_, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(self.map_access(item_names(self.obj)))
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let names = fields
+            .iter()
+            .filter(|field| self.obj.item(**field).is_some())
+            .map(|field| field.to_string())
+            .collect();
+        visitor.visit_map(self.map_access(names))
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // The top level object and array elements always exist, `None` can
+        // only be stored as a missing key (see `ItemDeserializer`).
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(MapAccessDeserializer::new(
+            self.map_access(item_names(self.obj)),
+        ))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+        ignored_any
+    }
+}
+
+struct ObjMapAccess<'a, 'p> {
+    obj: &'a DataObj<'p>,
+    path: &'a str,
+    names: std::vec::IntoIter<String>,
+    current: Option<String>,
+}
+
+impl<'de> de::MapAccess<'de> for ObjMapAccess<'_, '_> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(name) = self.names.next() else {
+            return Ok(None);
+        };
+        let key = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(name.as_str()))?;
+        self.current = Some(name);
+        Ok(Some(key))
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let name = self
+            .current
+            .take()
+            .expect("next_value_seed called before next_key_seed");
+        let path = join_path(self.path, &name);
+        seed.deserialize(ItemDeserializer {
+            obj: self.obj,
+            name,
+            path,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len())
+    }
+}
+
+struct ArraySeqAccess<'a, 'p> {
+    array: DataArray<'p>,
+    path: &'a str,
+    index: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for ArraySeqAccess<'_, '_> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        if self.index >= self.array.len() {
+            return Ok(None);
+        }
+        let path = format!("{}[{}]", self.path, self.index);
+        let obj = self
+            .array
+            .get(self.index)
+            .ok_or_else(|| <Error as de::Error>::custom(format!("missing value `{}`", path)))?;
+        self.index += 1;
+        seed.deserialize(ObjDeserializer { obj: &obj, path })
+            .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.array.len() - self.index)
+    }
+}
+
+/// Deserializes a single value of an object.
+struct ItemDeserializer<'a, 'p> {
+    obj: &'a DataObj<'p>,
+    name: String,
+    path: String,
+}
+
+impl ItemDeserializer<'_, '_> {
+    fn typ(&self) -> Result<DataType> {
+        item_type(self.obj, &self.name)?
+            .ok_or_else(|| de::Error::custom(format!("missing value `{}`", self.path)))
+    }
+
+    /// Whether the item has a user or default value. Serializing `None`
+    /// only unsets the user value, so the item itself may still exist.
+    fn has_value(&self) -> Result<bool> {
+        let name = self.name.as_str().try_into_obs_string()?;
+        Ok(self
+            .obj
+            .item(name)
+            .is_some_and(|item| item.has_user_value() || item.has_default_value()))
+    }
+
+    fn expect(&self, expected: DataType, name: &'static str) -> Result<()> {
+        let typ = self.typ()?;
+        if typ == expected {
+            Ok(())
+        } else {
+            Err(Error::DataTypeMismatch(self.path.clone(), name, typ))
+        }
+    }
+
+    fn value<T: super::FromDataItem>(&self) -> Result<T> {
+        self.obj
+            .get(self.name.as_str())
+            .ok_or_else(|| de::Error::custom(format!("missing value `{}`", self.path)))
+    }
+
+    fn string(&self) -> Result<String> {
+        self.expect(DataType::String, "string")?;
+        Ok(self.value::<std::borrow::Cow<str>>()?.into_owned())
+    }
+
+    fn object(&self) -> Result<DataObj<'static>> {
+        self.expect(DataType::Object, "object")?;
+        self.value()
+    }
+}
+
+macro_rules! deserialize_int {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                self.expect(DataType::Int, "integer")?;
+                visitor.visit_i64(self.value()?)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for ItemDeserializer<'_, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.typ()? {
+            DataType::String => visitor.visit_string(self.string()?),
+            DataType::Int => visitor.visit_i64(self.value()?),
+            DataType::Double => visitor.visit_f64(self.value()?),
+            DataType::Boolean => visitor.visit_bool(self.value()?),
+            DataType::Object => self.deserialize_map(visitor),
+            DataType::Array => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.expect(DataType::Boolean, "boolean")?;
+        visitor.visit_bool(self.value()?)
+    }
+
+    deserialize_int!(
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+    );
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        // Whole numbers loaded from JSON are stored as integers.
+        match self.typ()? {
+            DataType::Double => visitor.visit_f64(self.value()?),
+            DataType::Int => visitor.visit_f64(self.value::<i64>()? as f64),
+            typ => Err(Error::DataTypeMismatch(self.path, "number", typ)),
+        }
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.string()?)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(de::Error::custom(format!(
+            "bytes are not supported `{}`",
+            self.path
+        )))
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.has_value()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.expect(DataType::Array, "array")?;
+        visitor.visit_seq(ArraySeqAccess {
+            array: self.value()?,
+            path: &self.path,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let obj = self.object()?;
+        de::Deserializer::deserialize_map(
+            ObjDeserializer {
+                obj: &obj,
+                path: self.path,
+            },
+            visitor,
+        )
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let obj = self.object()?;
+        de::Deserializer::deserialize_struct(
+            ObjDeserializer {
+                obj: &obj,
+                path: self.path,
+            },
+            name,
+            fields,
+            visitor,
+        )
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.typ()? {
+            DataType::String => visitor.visit_enum(self.string()?.into_deserializer()),
+            DataType::Object => {
+                let obj = self.object()?;
+                de::Deserializer::deserialize_enum(
+                    ObjDeserializer {
+                        obj: &obj,
+                        path: self.path,
+                    },
+                    name,
+                    variants,
+                    visitor,
+                )
+            }
+            typ => Err(Error::DataTypeMismatch(self.path, "string or object", typ)),
+        }
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// A serialized value, written into its parent object under a key.
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(ObsString),
+    Object(DataObj<'static>),
+    Array(DataArray<'static>),
+}
+
+impl Value {
+    fn write(self, obj: &mut DataObj, name: ObsString) -> Result<()> {
+        match self {
+            Value::Null => {
+                obj.unset_user_value(name);
+                Ok(())
+            }
+            Value::Bool(v) => obj.set::<bool>(name, v),
+            Value::Int(v) => obj.set::<i64>(name, v),
+            Value::Double(v) => obj.set::<f64>(name, v),
+            Value::String(v) => obj.set::<ObsString>(name, v),
+            Value::Object(v) => obj.set::<DataObj>(name, v),
+            Value::Array(v) => obj.set::<DataArray>(name, v),
+        }
+    }
+
+    fn wrap(self, variant: Option<&'static str>) -> Result<Self> {
+        match variant {
+            Some(variant) => {
+                let mut obj = DataObj::new();
+                self.write(&mut obj, ObsString::from(variant))?;
+                Ok(Value::Object(obj))
+            }
+            None => Ok(self),
+        }
+    }
+}
+
+fn key_name<T: Serialize + ?Sized>(key: &T) -> Result<ObsString> {
+    match key.serialize(ValueSerializer)? {
+        Value::String(name) => Ok(name),
+        _ => Err(ser::Error::custom("object keys must be strings")),
+    }
+}
+
+/// Serializes the top level value directly into an existing object.
+struct ObjSerializer<'a, 'p> {
+    obj: &'a mut DataObj<'p>,
+}
+
+fn top_level_error() -> Error {
+    ser::Error::custom("only structs and maps can be written into an object")
+}
+
+macro_rules! serialize_top_level_error {
+    ($($method:ident: $t:ty),*) => {
+        $(
+            fn $method(self, _v: $t) -> Result<()> {
+                Err(top_level_error())
+            }
+        )*
+    };
+}
+
+impl<'a, 'p> ser::Serializer for ObjSerializer<'a, 'p> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = MapSerializer<&'a mut DataObj<'p>>;
+    type SerializeStruct = MapSerializer<&'a mut DataObj<'p>>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_top_level_error!(
+        serialize_bool: bool, serialize_i8: i8, serialize_i16: i16, serialize_i32: i32,
+        serialize_i64: i64, serialize_u8: u8, serialize_u16: u16, serialize_u32: u32,
+        serialize_u64: u64, serialize_f32: f32, serialize_f64: f64, serialize_char: char,
+        serialize_str: &str, serialize_bytes: &[u8], serialize_unit_struct: &'static str
+    );
+
+    fn serialize_none(self) -> Result<()> {
+        Err(top_level_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Err(top_level_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        Err(top_level_error())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value
+            .serialize(ValueSerializer)?
+            .write(self.obj, ObsString::from(variant))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(top_level_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(top_level_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(top_level_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(top_level_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(MapSerializer::new(self.obj, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(MapSerializer::new(self.obj, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(top_level_error())
+    }
+}
+
+/// Serializes a nested value into a [`Value`].
+struct ValueSerializer;
+
+macro_rules! serialize_value {
+    ($($method:ident: $t:ty => $variant:ident as $cast:ty),*) => {
+        $(
+            fn $method(self, v: $t) -> Result<Value> {
+                Ok(Value::$variant(v as $cast))
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer<DataObj<'static>>;
+    type SerializeStruct = MapSerializer<DataObj<'static>>;
+    type SerializeStructVariant = MapSerializer<DataObj<'static>>;
+
+    serialize_value!(
+        serialize_bool: bool => Bool as bool,
+        serialize_i8: i8 => Int as i64, serialize_i16: i16 => Int as i64,
+        serialize_i32: i32 => Int as i64, serialize_i64: i64 => Int as i64,
+        serialize_u8: u8 => Int as i64, serialize_u16: u16 => Int as i64,
+        serialize_u32: u32 => Int as i64,
+        serialize_f32: f32 => Double as f64, serialize_f64: f64 => Double as f64
+    );
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        i64::try_from(v)
+            .map(Value::Int)
+            .map_err(|_| ser::Error::custom(format!("integer {} is out of range", v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::String(v.try_into_obs_string()?))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Value> {
+        Err(ser::Error::custom("bytes are not supported"))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)?.wrap(Some(variant))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(None))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(None))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SeqSerializer> {
+        Ok(SeqSerializer::new(Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(MapSerializer::new(DataObj::new(), None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(MapSerializer::new(DataObj::new(), None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(MapSerializer::new(DataObj::new(), Some(variant)))
+    }
+}
+
+struct SeqSerializer {
+    array: DataArray<'static>,
+    variant: Option<&'static str>,
+}
+
+impl SeqSerializer {
+    fn new(variant: Option<&'static str>) -> Self {
+        Self {
+            array: DataArray::new(),
+            variant,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match value.serialize(ValueSerializer)? {
+            Value::Object(obj) => {
+                self.array.push(&obj);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("arrays can only contain objects")),
+        }
+    }
+
+    fn finish(self) -> Result<Value> {
+        Value::Array(self.array).wrap(self.variant)
+    }
+}
+
+macro_rules! impl_seq_serializer {
+    ($($trait:ident::$method:ident),*) => {
+        $(
+            impl ser::$trait for SeqSerializer {
+                type Ok = Value;
+                type Error = Error;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+                    self.push(value)
+                }
+
+                fn end(self) -> Result<Value> {
+                    self.finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_seq_serializer!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+/// Writes fields into an object, either borrowed for the top level value or
+/// owned for nested ones.
+struct MapSerializer<O> {
+    obj: O,
+    variant: Option<&'static str>,
+    next_key: Option<ObsString>,
+}
+
+impl<O> MapSerializer<O> {
+    fn new(obj: O, variant: Option<&'static str>) -> Self {
+        Self {
+            obj,
+            variant,
+            next_key: None,
+        }
+    }
+}
+
+trait MapTarget {
+    type Ok;
+
+    fn write(&mut self, name: ObsString, value: Value) -> Result<()>;
+    fn finish(self, variant: Option<&'static str>) -> Result<Self::Ok>;
+}
+
+impl MapTarget for &mut DataObj<'_> {
+    type Ok = ();
+
+    fn write(&mut self, name: ObsString, value: Value) -> Result<()> {
+        value.write(self, name)
+    }
+
+    fn finish(self, _variant: Option<&'static str>) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl MapTarget for DataObj<'static> {
+    type Ok = Value;
+
+    fn write(&mut self, name: ObsString, value: Value) -> Result<()> {
+        value.write(self, name)
+    }
+
+    fn finish(self, variant: Option<&'static str>) -> Result<Value> {
+        Value::Object(self).wrap(variant)
+    }
+}
+
+impl<O: MapTarget> ser::SerializeMap for MapSerializer<O> {
+    type Ok = O::Ok;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.next_key = Some(key_name(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let name = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        let value = value.serialize(ValueSerializer)?;
+        self.obj.write(name, value)
+    }
+
+    fn end(self) -> Result<O::Ok> {
+        self.obj.finish(self.variant)
+    }
+}
+
+impl<O: MapTarget> ser::SerializeStruct for MapSerializer<O> {
+    type Ok = O::Ok;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let value = value.serialize(ValueSerializer)?;
+        self.obj.write(key.try_into_obs_string()?, value)
+    }
+
+    fn end(self) -> Result<O::Ok> {
+        self.obj.finish(self.variant)
+    }
+}
+
+impl<O: MapTarget> ser::SerializeStructVariant for MapSerializer<O> {
+    type Ok = O::Ok;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<O::Ok> {
+        ser::SerializeStruct::end(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Fill,
+        Fit,
+        Scale { factor: f64 },
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        name: String,
+        value: i32,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        path: String,
+        width: u32,
+        opacity: f64,
+        enabled: bool,
+        mode: Mode,
+        scale: Mode,
+        inner: Inner,
+        items: Vec<Inner>,
+        label: Option<String>,
+        limit: Option<i64>,
+    }
+
+    fn settings() -> Settings {
+        Settings {
+            path: "/tmp/image.png".to_string(),
+            width: 1920,
+            opacity: 0.5,
+            enabled: true,
+            mode: Mode::Fit,
+            scale: Mode::Scale { factor: 2.0 },
+            inner: Inner {
+                name: "inner".to_string(),
+                value: -3,
+            },
+            items: vec![
+                Inner {
+                    name: "first".to_string(),
+                    value: 1,
+                },
+                Inner {
+                    name: "second".to_string(),
+                    value: 2,
+                },
+            ],
+            label: Some("label".to_string()),
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn round_trip_struct() {
+        let value = settings();
+        let obj = DataObj::from_serialize(&value).unwrap();
+        assert_eq!(obj.deserialize::<Settings>().unwrap(), value);
+    }
+
+    #[test]
+    fn nested_object_and_array() {
+        let obj = DataObj::from_serialize(&settings()).unwrap();
+
+        let inner: DataObj = obj.get("inner").unwrap();
+        assert_eq!(inner.get::<i64>("value"), Some(-3));
+
+        let items: DataArray = obj.get("items").unwrap();
+        assert_eq!(items.len(), 2);
+        let second = items.get(1).unwrap();
+        assert_eq!(second.deserialize::<Inner>().unwrap().name, "second");
+    }
+
+    #[test]
+    fn option_none_unsets_value() {
+        let mut value = settings();
+        let mut obj = DataObj::from_serialize(&value).unwrap();
+        assert_eq!(obj.get::<i64>("limit"), None);
+
+        value.label = None;
+        value.limit = Some(10);
+        obj.serialize(&value).unwrap();
+        assert!(!obj.has_user_value("label"));
+        assert_eq!(obj.deserialize::<Settings>().unwrap(), value);
+    }
+
+    #[test]
+    fn option_falls_back_to_default() {
+        let mut value = settings();
+        value.limit = None;
+        let mut obj = DataObj::from_serialize(&value).unwrap();
+        obj.set_default::<i64>("limit", 5).unwrap();
+
+        assert_eq!(obj.deserialize::<Settings>().unwrap().limit, Some(5));
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let mut obj = DataObj::from_serialize(&settings()).unwrap();
+        obj.set::<ObsString>("width", ObsString::from("wide"))
+            .unwrap();
+
+        match obj.deserialize::<Settings>() {
+            Err(Error::DataTypeMismatch(path, expected, found)) => {
+                assert_eq!(path, "width");
+                assert_eq!(expected, "integer");
+                assert_eq!(found, DataType::String);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn nested_type_mismatch() {
+        let mut obj = DataObj::from_serialize(&settings()).unwrap();
+        let mut inner: DataObj = obj.get("inner").unwrap();
+        inner.set::<bool>("name", true).unwrap();
+        obj.set::<DataObj>("inner", inner).unwrap();
+
+        match obj.deserialize::<Settings>() {
+            Err(Error::DataTypeMismatch(path, expected, found)) => {
+                assert_eq!(path, "inner.name");
+                assert_eq!(expected, "string");
+                assert_eq!(found, DataType::Boolean);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sequence_of_primitives_is_rejected() {
+        #[derive(Serialize)]
+        struct Numbers {
+            values: Vec<i32>,
+        }
+
+        assert!(DataObj::from_serialize(&Numbers { values: vec![1, 2] }).is_err());
+    }
+}
@@ -2,6 +2,7 @@ pub mod display;
 
 use crate::{native_enum, Error, Result};
 use core::convert::TryFrom;
+use core::mem::ManuallyDrop;
 use core::ptr::null_mut;
 use obs_sys::{
     gs_address_mode, gs_address_mode_GS_ADDRESS_BORDER, gs_address_mode_GS_ADDRESS_CLAMP,
@@ -13,9 +14,11 @@ use obs_sys::{
     gs_color_format_GS_R8, gs_color_format_GS_R8G8, gs_color_format_GS_RG16F,
     gs_color_format_GS_RG32F, gs_color_format_GS_RGBA, gs_color_format_GS_RGBA16,
     gs_color_format_GS_RGBA16F, gs_color_format_GS_RGBA32F, gs_color_format_GS_UNKNOWN,
-    gs_effect_create, gs_effect_destroy, gs_effect_get_param_by_name, gs_effect_get_param_info,
-    gs_effect_param_info, gs_effect_set_next_sampler, gs_effect_set_texture, gs_effect_set_vec2,
-    gs_effect_t, gs_eparam_t, gs_sample_filter, gs_sample_filter_GS_FILTER_ANISOTROPIC,
+    gs_color_space, gs_color_space_GS_CS_709_EXTENDED, gs_color_space_GS_CS_709_SCRGB,
+    gs_color_space_GS_CS_SRGB, gs_color_space_GS_CS_SRGB_16F, gs_effect_create, gs_effect_destroy,
+    gs_effect_get_param_by_name, gs_effect_get_param_info, gs_effect_param_info,
+    gs_effect_set_next_sampler, gs_effect_set_texture, gs_effect_set_vec2, gs_effect_t,
+    gs_eparam_t, gs_sample_filter, gs_sample_filter_GS_FILTER_ANISOTROPIC,
     gs_sample_filter_GS_FILTER_LINEAR, gs_sample_filter_GS_FILTER_MIN_LINEAR_MAG_MIP_POINT,
     gs_sample_filter_GS_FILTER_MIN_LINEAR_MAG_POINT_MIP_LINEAR,
     gs_sample_filter_GS_FILTER_MIN_MAG_LINEAR_MIP_POINT,
@@ -339,6 +342,36 @@ native_enum!(GraphicsColorFormat, gs_color_format {
     R8G8 => GS_R8G8,
 });
 
+native_enum!(
+/// The color space a source renders in, see
+/// [`VideoColorSpaceSource`](crate::source::VideoColorSpaceSource).
+ColorSpace, gs_color_space {
+    /// SDR
+    SRGB => GS_CS_SRGB,
+    /// High precision SDR
+    SRGB16F => GS_CS_SRGB_16F,
+    /// Canvas, SDR white level = 1.0
+    Rec709Extended => GS_CS_709_EXTENDED,
+    /// 1.0 = 80 nits
+    Rec709ScRGB => GS_CS_709_SCRGB,
+});
+
+impl ColorSpace {
+    /// Whether colors may go above SDR white.
+    pub fn is_hdr(&self) -> bool {
+        matches!(self, ColorSpace::Rec709Extended | ColorSpace::Rec709ScRGB)
+    }
+
+    /// The format of textures which hold this color space without losing
+    /// precision, like `gs_get_format_from_space`.
+    pub fn color_format(&self) -> GraphicsColorFormat {
+        match self {
+            ColorSpace::SRGB => GraphicsColorFormat::RGBA,
+            _ => GraphicsColorFormat::RGBA16F,
+        }
+    }
+}
+
 native_enum!(GraphicsAllowDirectRendering, obs_allow_direct_render {
     NoDirectRendering => OBS_NO_DIRECT_RENDERING,
     AllowDirectRendering => OBS_ALLOW_DIRECT_RENDERING,
@@ -556,6 +589,16 @@ impl GraphicsTexture {
     pub fn as_ptr(&self) -> *mut gs_texture_t {
         self.raw
     }
+
+    /// Wraps a texture owned by OBS, which is not destroyed when the wrapper
+    /// is dropped.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid texture for as long as the wrapper is used.
+    pub(crate) unsafe fn from_raw_borrowed(raw: *mut gs_texture_t) -> ManuallyDrop<Self> {
+        ManuallyDrop::new(Self { raw })
+    }
 }
 
 impl Drop for GraphicsTexture {
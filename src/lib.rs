@@ -47,14 +47,12 @@
 //! // source.
 //! // It allows you to specify the source ID and type.
 //! impl Sourceable for TestSource {
+//!     const TYPE: SourceType = SourceType::Filter;
+//!
 //!     fn get_id() -> ObsString {
 //!         obs_string!("test_source")
 //!     }
 //!
-//!     fn get_type() -> SourceType {
-//!         SourceType::Filter
-//!     }
-//!
 //!     fn create(
 //!         create: &mut CreatableSourceContext<Self>,
 //!         _source: SourceRef
@@ -143,6 +141,8 @@ pub mod output;
 pub mod properties;
 /// Error handling
 pub mod result;
+/// Typed settings shared by properties, defaults and updates
+pub mod settings;
 /// Tools for creating sources
 pub mod source;
 /// String macros
@@ -152,7 +152,7 @@ mod native_enum;
 
 /// Re-exports of a bunch of popular tools
 pub mod prelude {
-    pub use crate::data::{DataArray, DataObj, FromDataItem};
+    pub use crate::data::{DataArray, DataItem, DataObj, FromDataItem, ToDataItem};
     pub use crate::module::*;
     pub use crate::source::context::*;
     pub use crate::string::*;
@@ -1,4 +1,72 @@
-use obs_sys::{audio_output_get_channels, audio_output_get_sample_rate, audio_t, obs_audio_data};
+use std::marker::PhantomData;
+
+use obs_sys::{
+    audio_format, audio_format_AUDIO_FORMAT_16BIT, audio_format_AUDIO_FORMAT_16BIT_PLANAR,
+    audio_format_AUDIO_FORMAT_32BIT, audio_format_AUDIO_FORMAT_32BIT_PLANAR,
+    audio_format_AUDIO_FORMAT_FLOAT, audio_format_AUDIO_FORMAT_FLOAT_PLANAR,
+    audio_format_AUDIO_FORMAT_U8BIT, audio_format_AUDIO_FORMAT_U8BIT_PLANAR,
+    audio_format_AUDIO_FORMAT_UNKNOWN, audio_output_data, audio_output_get_channels,
+    audio_output_get_sample_rate, audio_t, obs_audio_data, obs_source_audio, obs_source_audio_mix,
+    speaker_layout, speaker_layout_SPEAKERS_2POINT1, speaker_layout_SPEAKERS_4POINT0,
+    speaker_layout_SPEAKERS_4POINT1, speaker_layout_SPEAKERS_5POINT1,
+    speaker_layout_SPEAKERS_7POINT1, speaker_layout_SPEAKERS_MONO, speaker_layout_SPEAKERS_STEREO,
+    speaker_layout_SPEAKERS_UNKNOWN, AUDIO_OUTPUT_FRAMES, MAX_AUDIO_CHANNELS, MAX_AUDIO_MIXES,
+    MAX_AV_PLANES,
+};
+
+use crate::{native_enum, Error, Result};
+
+native_enum!(AudioFormat, audio_format {
+    Unknown => AUDIO_FORMAT_UNKNOWN,
+    U8 => AUDIO_FORMAT_U8BIT,
+    I16 => AUDIO_FORMAT_16BIT,
+    I32 => AUDIO_FORMAT_32BIT,
+    F32 => AUDIO_FORMAT_FLOAT,
+    U8Planar => AUDIO_FORMAT_U8BIT_PLANAR,
+    I16Planar => AUDIO_FORMAT_16BIT_PLANAR,
+    I32Planar => AUDIO_FORMAT_32BIT_PLANAR,
+    F32Planar => AUDIO_FORMAT_FLOAT_PLANAR,
+});
+
+native_enum!(SpeakerLayout, speaker_layout {
+    Unknown => SPEAKERS_UNKNOWN,
+    Mono => SPEAKERS_MONO,
+    Stereo => SPEAKERS_STEREO,
+    TwoPointOne => SPEAKERS_2POINT1,
+    FourPointZero => SPEAKERS_4POINT0,
+    FourPointOne => SPEAKERS_4POINT1,
+    FivePointOne => SPEAKERS_5POINT1,
+    SevenPointOne => SPEAKERS_7POINT1,
+});
+
+impl SpeakerLayout {
+    pub fn channels(&self) -> usize {
+        match self {
+            SpeakerLayout::Unknown => 0,
+            SpeakerLayout::Mono => 1,
+            SpeakerLayout::Stereo => 2,
+            SpeakerLayout::TwoPointOne => 3,
+            SpeakerLayout::FourPointZero => 4,
+            SpeakerLayout::FourPointOne => 5,
+            SpeakerLayout::FivePointOne => 6,
+            SpeakerLayout::SevenPointOne => 8,
+        }
+    }
+
+    /// The layout OBS uses for `channels` channels.
+    pub fn from_channels(channels: usize) -> Self {
+        match channels {
+            1 => SpeakerLayout::Mono,
+            2 => SpeakerLayout::Stereo,
+            3 => SpeakerLayout::TwoPointOne,
+            4 => SpeakerLayout::FourPointZero,
+            5 => SpeakerLayout::FourPointOne,
+            6 => SpeakerLayout::FivePointOne,
+            8 => SpeakerLayout::SevenPointOne,
+            _ => SpeakerLayout::Unknown,
+        }
+    }
+}
 
 pub struct AudioDataContext {
     pointer: *mut obs_audio_data,
@@ -76,3 +144,289 @@ impl AudioRef {
         unsafe { audio_output_get_channels(self.pointer) }
     }
 }
+
+/// Planar float buffers of a single audio mix, each channel holding
+/// [`AudioMix::frames`] samples.
+pub struct AudioMix<'a> {
+    data: &'a mut audio_output_data,
+    channels: usize,
+}
+
+impl<'a> AudioMix<'a> {
+    fn new(data: &'a mut audio_output_data, channels: usize) -> Self {
+        Self {
+            data,
+            channels: channels.min(MAX_AUDIO_CHANNELS as usize),
+        }
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn frames(&self) -> usize {
+        AUDIO_OUTPUT_FRAMES as usize
+    }
+
+    fn channel_ptr(&self, channel: usize) -> Option<*mut f32> {
+        if channel >= self.channels {
+            return None;
+        }
+        let ptr = self.data.data[channel];
+        (!ptr.is_null()).then_some(ptr)
+    }
+
+    pub fn channel(&self, channel: usize) -> Option<&[f32]> {
+        let ptr = self.channel_ptr(channel)?;
+        Some(unsafe { std::slice::from_raw_parts(ptr, self.frames()) })
+    }
+
+    pub fn channel_mut(&mut self, channel: usize) -> Option<&mut [f32]> {
+        let ptr = self.channel_ptr(channel)?;
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr, self.frames()) })
+    }
+}
+
+/// Output buffers of [`AudioRenderSource`], one [`AudioMix`] for every
+/// mixer (audio track) the source is rendered to.
+///
+/// [`AudioRenderSource`]: crate::source::traits::AudioRenderSource
+pub struct AudioRenderContext<'a> {
+    output: &'a mut obs_source_audio_mix,
+    timestamp: &'a mut u64,
+    mixers: u32,
+    channels: usize,
+    sample_rate: usize,
+}
+
+impl<'a> AudioRenderContext<'a> {
+    pub(crate) fn new(
+        output: &'a mut obs_source_audio_mix,
+        timestamp: &'a mut u64,
+        mixers: u32,
+        channels: usize,
+        sample_rate: usize,
+    ) -> Self {
+        Self {
+            output,
+            timestamp,
+            mixers,
+            channels,
+            sample_rate,
+        }
+    }
+
+    /// Bit mask of the mixers which are rendered.
+    pub fn mixers(&self) -> u32 {
+        self.mixers
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+
+    /// Returns the buffers of `mixer`, or `None` if it isn't rendered.
+    pub fn mix_mut(&mut self, mixer: usize) -> Option<AudioMix<'_>> {
+        if mixer >= MAX_AUDIO_MIXES as usize || self.mixers & (1 << mixer) == 0 {
+            return None;
+        }
+        Some(AudioMix::new(&mut self.output.output[mixer], self.channels))
+    }
+
+    /// Sets the timestamp of the rendered audio.
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        *self.timestamp = timestamp;
+    }
+}
+
+/// Output buffers of [`AudioMixSource`].
+///
+/// [`AudioMixSource`]: crate::source::traits::AudioMixSource
+pub struct AudioMixContext<'a> {
+    output: &'a mut audio_output_data,
+    timestamp: &'a mut u64,
+    channels: usize,
+    sample_rate: usize,
+}
+
+impl<'a> AudioMixContext<'a> {
+    pub(crate) fn new(
+        output: &'a mut audio_output_data,
+        timestamp: &'a mut u64,
+        channels: usize,
+        sample_rate: usize,
+    ) -> Self {
+        Self {
+            output,
+            timestamp,
+            channels,
+            sample_rate,
+        }
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+
+    pub fn mix_mut(&mut self) -> AudioMix<'_> {
+        AudioMix::new(self.output, self.channels)
+    }
+
+    /// Sets the timestamp of the mixed audio.
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        *self.timestamp = timestamp;
+    }
+}
+
+/// A sample type which can be output with [`SourceAudio`].
+pub trait AudioSample: Copy {
+    const INTERLEAVED: AudioFormat;
+    const PLANAR: AudioFormat;
+}
+
+impl AudioSample for f32 {
+    const INTERLEAVED: AudioFormat = AudioFormat::F32;
+    const PLANAR: AudioFormat = AudioFormat::F32Planar;
+}
+
+impl AudioSample for i16 {
+    const INTERLEAVED: AudioFormat = AudioFormat::I16;
+    const PLANAR: AudioFormat = AudioFormat::I16Planar;
+}
+
+/// Audio samples which async sources push to OBS with
+/// [`SourceRef::output_audio`](crate::source::SourceRef::output_audio).
+///
+/// The samples are only borrowed, OBS copies them when they are output.
+pub struct SourceAudio<'a> {
+    data: [*const u8; MAX_AV_PLANES as usize],
+    frames: u32,
+    format: AudioFormat,
+    speakers: SpeakerLayout,
+    sample_rate: u32,
+    timestamp: u64,
+    _marker: PhantomData<&'a [u8]>,
+}
+
+impl<'a> SourceAudio<'a> {
+    /// Audio with one slice of samples per channel, at 48 kHz.
+    /// [`SpeakerLayout::from_channels`] gives the usual layout for a number
+    /// of channels.
+    ///
+    /// Fails with [`Error::AudioLayoutMismatch`] if `speakers` doesn't have
+    /// one channel per slice, or the slices don't have the same length.
+    pub fn planar<S: AudioSample>(channels: &[&'a [S]], speakers: SpeakerLayout) -> Result<Self> {
+        if speakers.channels() == 0 {
+            return Err(Error::AudioLayoutMismatch("unknown speaker layout"));
+        }
+        if channels.len() != speakers.channels() {
+            return Err(Error::AudioLayoutMismatch(
+                "channels don't match the speaker layout",
+            ));
+        }
+        let frames = channels.first().map_or(0, |channel| channel.len());
+        if channels.iter().any(|channel| channel.len() != frames) {
+            return Err(Error::AudioLayoutMismatch(
+                "channels have different lengths",
+            ));
+        }
+
+        let mut data = [std::ptr::null(); MAX_AV_PLANES as usize];
+        for (plane, channel) in data.iter_mut().zip(channels) {
+            *plane = channel.as_ptr() as *const u8;
+        }
+
+        Self::new(data, frames, S::PLANAR, speakers)
+    }
+
+    /// Audio with the samples of every channel interleaved, at 48 kHz.
+    ///
+    /// Fails with [`Error::AudioLayoutMismatch`] if `speakers` is
+    /// [`SpeakerLayout::Unknown`], or the samples don't contain whole frames.
+    pub fn interleaved<S: AudioSample>(samples: &'a [S], speakers: SpeakerLayout) -> Result<Self> {
+        let channels = speakers.channels();
+        if channels == 0 {
+            return Err(Error::AudioLayoutMismatch("unknown speaker layout"));
+        }
+        if !samples.len().is_multiple_of(channels) {
+            return Err(Error::AudioLayoutMismatch(
+                "samples don't contain whole frames",
+            ));
+        }
+
+        let mut data = [std::ptr::null(); MAX_AV_PLANES as usize];
+        data[0] = samples.as_ptr() as *const u8;
+
+        Self::new(data, samples.len() / channels, S::INTERLEAVED, speakers)
+    }
+
+    fn new(
+        data: [*const u8; MAX_AV_PLANES as usize],
+        frames: usize,
+        format: AudioFormat,
+        speakers: SpeakerLayout,
+    ) -> Result<Self> {
+        let frames =
+            u32::try_from(frames).map_err(|_| Error::AudioLayoutMismatch("too many frames"))?;
+        Ok(Self {
+            data,
+            frames,
+            format,
+            speakers,
+            sample_rate: 48000,
+            timestamp: 0,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// The timestamp of the first frame in nanoseconds, e.g. from
+    /// `os_gettime_ns`.
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames as usize
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    pub fn speakers(&self) -> SpeakerLayout {
+        self.speakers
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub(crate) fn as_raw(&self) -> obs_source_audio {
+        obs_source_audio {
+            data: self.data,
+            frames: self.frames,
+            speakers: self.speakers.as_raw(),
+            format: self.format.as_raw(),
+            samples_per_sec: self.sample_rate,
+            timestamp: self.timestamp,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use obs_sys::{
-    obs_source_frame, video_data, video_format, video_format_VIDEO_FORMAT_AYUV,
+    obs_source_frame, obs_source_frame2, video_colorspace, video_colorspace_VIDEO_CS_2100_HLG,
+    video_colorspace_VIDEO_CS_2100_PQ, video_colorspace_VIDEO_CS_601,
+    video_colorspace_VIDEO_CS_709, video_colorspace_VIDEO_CS_DEFAULT,
+    video_colorspace_VIDEO_CS_SRGB, video_data, video_format, video_format_VIDEO_FORMAT_AYUV,
     video_format_VIDEO_FORMAT_BGR3, video_format_VIDEO_FORMAT_BGRA, video_format_VIDEO_FORMAT_BGRX,
     video_format_VIDEO_FORMAT_I010, video_format_VIDEO_FORMAT_I210, video_format_VIDEO_FORMAT_I40A,
     video_format_VIDEO_FORMAT_I412, video_format_VIDEO_FORMAT_I420, video_format_VIDEO_FORMAT_I422,
@@ -7,8 +10,10 @@ use obs_sys::{
     video_format_VIDEO_FORMAT_NV12, video_format_VIDEO_FORMAT_P010, video_format_VIDEO_FORMAT_RGBA,
     video_format_VIDEO_FORMAT_UYVY, video_format_VIDEO_FORMAT_Y800, video_format_VIDEO_FORMAT_YA2L,
     video_format_VIDEO_FORMAT_YUVA, video_format_VIDEO_FORMAT_YUY2, video_format_VIDEO_FORMAT_YVYU,
-    video_output_get_format, video_output_get_frame_rate, video_output_get_height,
-    video_output_get_width, video_t,
+    video_format_get_parameters_for_format, video_output_get_format, video_output_get_frame_rate,
+    video_output_get_height, video_output_get_width, video_range_type,
+    video_range_type_VIDEO_RANGE_DEFAULT, video_range_type_VIDEO_RANGE_FULL,
+    video_range_type_VIDEO_RANGE_PARTIAL, video_t,
 };
 
 use crate::native_enum;
@@ -63,6 +68,21 @@ native_enum!(VideoFormat, video_format {
     YA2L => VIDEO_FORMAT_YA2L,
 });
 
+native_enum!(VideoRangeType, video_range_type {
+    Default => VIDEO_RANGE_DEFAULT,
+    Partial => VIDEO_RANGE_PARTIAL,
+    Full => VIDEO_RANGE_FULL,
+});
+
+native_enum!(VideoColorSpace, video_colorspace {
+    Default => VIDEO_CS_DEFAULT,
+    BT601 => VIDEO_CS_601,
+    BT709 => VIDEO_CS_709,
+    SRGB => VIDEO_CS_SRGB,
+    BT2100PQ => VIDEO_CS_2100_PQ,
+    BT2100HLG => VIDEO_CS_2100_HLG,
+});
+
 pub struct VideoDataSourceContext {
     pointer: *mut obs_source_frame,
 }
@@ -139,6 +159,20 @@ pub enum FrameSize {
     FourPlane(usize, usize, usize, usize),
 }
 
+impl FrameSize {
+    /// The size of each plane, in bytes.
+    pub fn planes(&self) -> Vec<usize> {
+        match *self {
+            FrameSize::Unknown => vec![],
+            FrameSize::Planes { size, count } => vec![size; count],
+            FrameSize::OnePlane(a) => vec![a],
+            FrameSize::TwoPlane(a, b) => vec![a, b],
+            FrameSize::ThreePlane(a, b, c) => vec![a, b, c],
+            FrameSize::FourPlane(a, b, c, d) => vec![a, b, c, d],
+        }
+    }
+}
+
 impl VideoInfo {
     /// see https://github.com/obsproject/obs-studio/blob/a1e8075fba09f3b56ed43ead64cc3e340dd7a059/libobs/media-io/video-frame.c#L23
     pub fn frame_size(&self) -> FrameSize {
@@ -156,7 +190,7 @@ impl VideoInfo {
         match format {
             VideoFormat::None => FrameSize::Planes { size: 0, count: 0 },
             I420 => FrameSize::ThreePlane(full_size, quarter_size, quarter_size),
-            NV12 => FrameSize::TwoPlane(full_size, half_size * 2),
+            NV12 => FrameSize::TwoPlane(full_size, quarter_size * 2),
             Y800 => FrameSize::OnePlane(full_size),
             YVYU | UYVY | YUY2 => FrameSize::OnePlane(half_size * 4),
             BGRX | BGRA | RGBA | AYUV => FrameSize::OnePlane(full_size * 4),
@@ -185,6 +219,36 @@ impl VideoInfo {
             P010 => FrameSize::TwoPlane(full_size * 2, quarter_size * 4),
         }
     }
+
+    /// The size of a row of each plane, in bytes, matching
+    /// [`frame_size`](Self::frame_size).
+    pub fn linesizes(&self) -> Vec<u32> {
+        use VideoFormat::*;
+        let width = self.width;
+        let half_width = width.div_ceil(2);
+        let Some(format) = self.format else {
+            return vec![];
+        };
+        match format {
+            VideoFormat::None => vec![],
+            I420 => vec![width, half_width, half_width],
+            // Interleaved U and V samples.
+            NV12 => vec![width, half_width * 2],
+            Y800 => vec![width],
+            YVYU | UYVY | YUY2 => vec![half_width * 4],
+            BGRX | BGRA | RGBA | AYUV => vec![width * 4],
+            I444 => vec![width; 3],
+            I412 => vec![width * 2; 3],
+            BGR3 => vec![width * 3],
+            I422 => vec![width, half_width, half_width],
+            I210 => vec![width * 2, half_width * 2, half_width * 2],
+            I40A | I42A => vec![width, half_width, half_width, width],
+            YUVA => vec![width; 4],
+            YA2L => vec![width * 2; 4],
+            I010 => vec![width * 2, half_width * 2, half_width * 2],
+            P010 => vec![width * 2, half_width * 4],
+        }
+    }
 }
 
 #[allow(unused)]
@@ -225,3 +289,193 @@ impl VideoRef {
         VideoFormat::from_raw(raw).ok()
     }
 }
+
+/// An owned video frame, which async sources push to OBS with
+/// [`SourceRef::output_video`](crate::source::SourceRef::output_video).
+///
+/// OBS copies the planes when the frame is output, so a frame can be filled
+/// and output again for every new image.
+pub struct SourceFrame {
+    format: VideoFormat,
+    width: u32,
+    height: u32,
+    planes: Vec<Vec<u8>>,
+    linesize: Vec<u32>,
+    timestamp: u64,
+    range: VideoRangeType,
+    color_space: VideoColorSpace,
+    flip: bool,
+}
+
+impl SourceFrame {
+    /// Creates a zeroed frame, with planes sized for `format`.
+    pub fn new(format: VideoFormat, width: u32, height: u32) -> Self {
+        let info = VideoInfo {
+            width,
+            height,
+            frame_rate: 0.,
+            format: Some(format),
+        };
+        let planes = info
+            .frame_size()
+            .planes()
+            .into_iter()
+            .map(|size| vec![0; size])
+            .collect();
+        let linesize = info.linesizes();
+
+        Self {
+            format,
+            width,
+            height,
+            planes,
+            linesize,
+            timestamp: 0,
+            range: VideoRangeType::Default,
+            color_space: VideoColorSpace::Default,
+            flip: false,
+        }
+    }
+
+    /// The timestamp of the frame in nanoseconds, e.g. from
+    /// `os_gettime_ns`.
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_range(mut self, range: VideoRangeType) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn with_color_space(mut self, color_space: VideoColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Flips the frame vertically.
+    pub fn with_flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: u64) {
+        self.timestamp = timestamp;
+    }
+
+    pub fn format(&self) -> VideoFormat {
+        self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn plane_count(&self) -> usize {
+        self.planes.len()
+    }
+
+    pub fn plane(&self, idx: usize) -> Option<&[u8]> {
+        self.planes.get(idx).map(Vec::as_slice)
+    }
+
+    pub fn plane_mut(&mut self, idx: usize) -> Option<&mut [u8]> {
+        self.planes.get_mut(idx).map(Vec::as_mut_slice)
+    }
+
+    /// The size of a row of the plane, in bytes.
+    pub fn linesize(&self, idx: usize) -> Option<u32> {
+        self.linesize.get(idx).copied()
+    }
+
+    /// Borrows the frame as the struct taken by `obs_source_output_video2`,
+    /// which only reads the planes.
+    pub(crate) fn as_raw(&self) -> obs_source_frame2 {
+        let mut raw = obs_source_frame2 {
+            data: [std::ptr::null_mut(); 8],
+            linesize: [0; 8],
+            width: self.width,
+            height: self.height,
+            timestamp: self.timestamp,
+            format: self.format.as_raw(),
+            range: self.range.as_raw(),
+            color_matrix: [0.; 16],
+            color_range_min: [0.; 3],
+            color_range_max: [0.; 3],
+            flip: self.flip,
+            flags: 0,
+            trc: 0,
+        };
+        for (idx, plane) in self.planes.iter().enumerate() {
+            raw.data[idx] = plane.as_ptr() as *mut u8;
+            raw.linesize[idx] = self.linesize[idx];
+        }
+        unsafe {
+            video_format_get_parameters_for_format(
+                self.color_space.as_raw(),
+                self.range.as_raw(),
+                raw.format,
+                raw.color_matrix.as_mut_ptr(),
+                raw.color_range_min.as_mut_ptr(),
+                raw.color_range_max.as_mut_ptr(),
+            );
+        }
+        raw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(format: VideoFormat, sizes: &[usize], linesizes: &[u32]) {
+        let frame = SourceFrame::new(format, 5, 3);
+        assert_eq!(frame.plane_count(), sizes.len());
+        for (idx, (&size, &linesize)) in sizes.iter().zip(linesizes).enumerate() {
+            assert_eq!(frame.plane(idx).map(<[u8]>::len), Some(size));
+            assert_eq!(frame.linesize(idx), Some(linesize));
+        }
+
+        let raw = frame.as_raw();
+        for (idx, (&data, &linesize)) in raw.data.iter().zip(&raw.linesize).enumerate() {
+            match frame.plane(idx) {
+                Some(plane) => {
+                    assert_eq!(data as *const u8, plane.as_ptr());
+                    assert_eq!(Some(linesize), frame.linesize(idx));
+                }
+                None => {
+                    assert!(data.is_null());
+                    assert_eq!(linesize, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn i420() {
+        // 3 rows of 5 luma samples, 2 rows of 3 samples per chroma plane.
+        check(VideoFormat::I420, &[15, 6, 6], &[5, 3, 3]);
+    }
+
+    #[test]
+    fn nv12() {
+        // 2 rows of 3 interleaved UV pairs.
+        check(VideoFormat::NV12, &[15, 12], &[5, 6]);
+    }
+
+    #[test]
+    fn packed() {
+        check(VideoFormat::BGRA, &[60], &[20]);
+        // YUY2 stores pairs of pixels in 4 bytes, rounding the width up.
+        check(VideoFormat::YUY2, &[36], &[12]);
+    }
+}
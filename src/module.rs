@@ -3,9 +3,9 @@ use crate::source::{traits::Sourceable, SourceInfo, SourceInfoBuilder};
 use crate::string::{DisplayExt as _, ObsString, TryIntoObsString as _};
 use crate::{Error, Result};
 use obs_sys::{
-    obs_get_module_author, obs_get_module_description, obs_get_module_file_name,
-    obs_get_module_name, obs_module_t, obs_output_info, obs_register_output_s,
-    obs_register_source_s, obs_source_info, size_t,
+    bfree, obs_get_module_author, obs_get_module_description, obs_get_module_file_name,
+    obs_get_module_name, obs_module_get_config_path, obs_module_t, obs_output_info,
+    obs_register_output_s, obs_register_source_s, obs_source_info, size_t,
 };
 use std::marker::PhantomData;
 
@@ -206,4 +206,19 @@ impl ModuleRef {
     pub fn file_name(&self) -> Result<ObsString> {
         unsafe { obs_get_module_file_name(self.raw) }.try_into_obs_string()
     }
+
+    /// Returns the path of `file` inside the config directory of this module,
+    /// e.g. to save settings with [`DataObj::save_json_safe`]. The directory
+    /// itself may not exist yet.
+    ///
+    /// [`DataObj::save_json_safe`]: crate::data::DataObj::save_json_safe
+    pub fn config_path(&self, file: impl Into<ObsString>) -> Result<ObsString> {
+        let file = file.into();
+        unsafe {
+            let ptr = obs_module_get_config_path(self.raw, file.as_ptr());
+            let path = (ptr as *const std::os::raw::c_char).try_into_obs_string();
+            bfree(ptr as *mut _);
+            path
+        }
+    }
 }
@@ -156,7 +156,10 @@ pub unsafe extern "C" fn get_properties<D: GetPropertiesOutput>(
     data: *mut ::std::os::raw::c_void,
 ) -> *mut obs_properties {
     let wrapper: &mut DataWrapper<D> = &mut *(data as *mut DataWrapper<D>);
-    let properties = D::get_properties(&mut wrapper.data);
+    let mut properties = D::get_properties(&mut wrapper.data);
+    if let Err(err) = unsafe { properties.bind_buttons(&mut wrapper.data) } {
+        log::error!("{}", err);
+    }
     properties.into_raw()
 }
 
@@ -1,35 +1,62 @@
 #![allow(non_upper_case_globals)]
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use crate::{native_enum, string::ObsString, wrapper::PtrWrapper};
+use crate::{data::DataObj, native_enum, string::ObsString, wrapper::PtrWrapper, Error, Result};
 use num_traits::{one, Bounded, Float, Num, NumCast, PrimInt, ToPrimitive};
 use obs_sys::{
-    obs_combo_format, obs_combo_format_OBS_COMBO_FORMAT_FLOAT,
+    media_frames_per_second, obs_combo_format, obs_combo_format_OBS_COMBO_FORMAT_FLOAT,
     obs_combo_format_OBS_COMBO_FORMAT_INT, obs_combo_format_OBS_COMBO_FORMAT_INVALID,
     obs_combo_format_OBS_COMBO_FORMAT_STRING, obs_combo_type,
     obs_combo_type_OBS_COMBO_TYPE_EDITABLE, obs_combo_type_OBS_COMBO_TYPE_INVALID,
-    obs_combo_type_OBS_COMBO_TYPE_LIST, obs_editable_list_type,
+    obs_combo_type_OBS_COMBO_TYPE_LIST, obs_data_t, obs_editable_list_type,
     obs_editable_list_type_OBS_EDITABLE_LIST_TYPE_FILES,
     obs_editable_list_type_OBS_EDITABLE_LIST_TYPE_FILES_AND_URLS,
-    obs_editable_list_type_OBS_EDITABLE_LIST_TYPE_STRINGS, obs_path_type,
+    obs_editable_list_type_OBS_EDITABLE_LIST_TYPE_STRINGS, obs_group_type,
+    obs_group_type_OBS_GROUP_CHECKABLE, obs_group_type_OBS_GROUP_NORMAL, obs_path_type,
     obs_path_type_OBS_PATH_DIRECTORY, obs_path_type_OBS_PATH_FILE,
-    obs_path_type_OBS_PATH_FILE_SAVE, obs_properties_add_bool, obs_properties_add_color,
-    obs_properties_add_editable_list, obs_properties_add_float, obs_properties_add_float_slider,
-    obs_properties_add_font, obs_properties_add_int, obs_properties_add_int_slider,
-    obs_properties_add_list, obs_properties_add_path, obs_properties_add_text,
-    obs_properties_create, obs_properties_destroy, obs_properties_t, obs_property_list_add_float,
-    obs_property_list_add_int, obs_property_list_add_string, obs_property_list_insert_float,
-    obs_property_list_insert_int, obs_property_list_insert_string, obs_property_list_item_disable,
-    obs_property_list_item_remove, obs_property_t, obs_text_type, obs_text_type_OBS_TEXT_DEFAULT,
-    obs_text_type_OBS_TEXT_MULTILINE, obs_text_type_OBS_TEXT_PASSWORD, size_t,
+    obs_path_type_OBS_PATH_FILE_SAVE, obs_properties_add_bool, obs_properties_add_button2,
+    obs_properties_add_color, obs_properties_add_color_alpha, obs_properties_add_editable_list,
+    obs_properties_add_float, obs_properties_add_float_slider, obs_properties_add_font,
+    obs_properties_add_frame_rate, obs_properties_add_group, obs_properties_add_int,
+    obs_properties_add_int_slider, obs_properties_add_list, obs_properties_add_path,
+    obs_properties_add_text, obs_properties_create, obs_properties_destroy, obs_properties_get,
+    obs_properties_get_param, obs_properties_set_param, obs_properties_t, obs_property_enabled,
+    obs_property_float_set_suffix, obs_property_frame_rate_fps_range_add,
+    obs_property_frame_rate_option_add, obs_property_get_type, obs_property_int_set_suffix,
+    obs_property_list_add_float, obs_property_list_add_int, obs_property_list_add_string,
+    obs_property_list_format, obs_property_list_insert_float, obs_property_list_insert_int,
+    obs_property_list_insert_string, obs_property_list_item_disable, obs_property_list_item_remove,
+    obs_property_set_enabled, obs_property_set_long_description,
+    obs_property_set_modified_callback2, obs_property_set_visible, obs_property_t,
+    obs_property_text_set_info_type, obs_property_text_set_info_word_wrap,
+    obs_property_type_OBS_PROPERTY_LIST, obs_property_visible, obs_text_info_type,
+    obs_text_info_type_OBS_TEXT_INFO_ERROR, obs_text_info_type_OBS_TEXT_INFO_NORMAL,
+    obs_text_info_type_OBS_TEXT_INFO_WARNING, obs_text_type, obs_text_type_OBS_TEXT_DEFAULT,
+    obs_text_type_OBS_TEXT_INFO, obs_text_type_OBS_TEXT_MULTILINE, obs_text_type_OBS_TEXT_PASSWORD,
+    size_t,
 };
 
-use std::{marker::PhantomData, ops::RangeBounds, os::raw::c_int};
+use std::{
+    any::type_name,
+    ffi::c_void,
+    marker::PhantomData,
+    mem::forget,
+    ops::{Deref, DerefMut, RangeBounds},
+    os::raw::c_int,
+};
 
 native_enum!(TextType, obs_text_type {
     Default => OBS_TEXT_DEFAULT,
     Password => OBS_TEXT_PASSWORD,
     Multiline => OBS_TEXT_MULTILINE,
+    /// Read-only text shown as a label, see [`TextProp::with_info_type`].
+    Info => OBS_TEXT_INFO,
+});
+
+native_enum!(TextInfoType, obs_text_info_type {
+    Normal => OBS_TEXT_INFO_NORMAL,
+    Warning => OBS_TEXT_INFO_WARNING,
+    Error => OBS_TEXT_INFO_ERROR,
 });
 
 native_enum!(PathType, obs_path_type {
@@ -57,6 +84,11 @@ native_enum!(EditableListType, obs_editable_list_type {
     FilesAndUrls => OBS_EDITABLE_LIST_TYPE_FILES_AND_URLS,
 });
 
+native_enum!(GroupType, obs_group_type {
+    Normal => OBS_GROUP_NORMAL,
+    Checkable => OBS_GROUP_CHECKABLE,
+});
+
 /// Wrapper around [`obs_properties_t`], which is used by
 /// OBS to generate a user-friendly configuration UI.
 pub struct Properties {
@@ -85,24 +117,33 @@ impl Properties {
         }
     }
 
+    /// Adds `prop` under the settings name `name`.
+    ///
+    /// Returns `None` if a property called `name` already exists.
     pub fn add<T: ObsProp>(
         &mut self,
         name: ObsString,
         description: ObsString,
         prop: T,
-    ) -> &mut Self {
+    ) -> Option<Property<'_>> {
         unsafe {
-            prop.add_to_props(self.pointer, name, description);
+            let raw = prop.add_to_props(self.pointer, name, description);
+            if raw.is_null() {
+                return None;
+            }
+            Some(Property::new(raw, self.pointer))
         }
-        self
     }
 
+    /// Adds a list of choices, see [`ListProp`].
+    ///
+    /// Returns `None` if a property called `name` already exists.
     pub fn add_list<T: ListType>(
         &mut self,
         name: ObsString,
         description: ObsString,
         editable: bool,
-    ) -> ListProp<T> {
+    ) -> Option<ListProp<'_, T>> {
         unsafe {
             let raw = obs_properties_add_list(
                 self.pointer,
@@ -116,63 +157,338 @@ impl Properties {
                 .into(),
                 T::format().into(),
             );
-            ListProp::from_raw(raw).expect("obs_properties_add_list")
+            if raw.is_null() {
+                return None;
+            }
+            Some(ListProp {
+                prop: Property::new(raw, self.pointer),
+                _type: PhantomData,
+            })
+        }
+    }
+
+    /// Adds `group` as a group of properties. A [`GroupType::Checkable`] group
+    /// also stores a `bool` setting under `name`.
+    ///
+    /// Returns `None` if a property called `name` already exists, or if
+    /// `group` contains a property with the same name as one of these
+    /// properties. `group` is dropped in that case.
+    pub fn add_group(
+        &mut self,
+        name: ObsString,
+        description: ObsString,
+        typ: GroupType,
+        group: Properties,
+    ) -> Option<Property<'_>> {
+        unsafe {
+            let raw = obs_properties_add_group(
+                self.pointer,
+                name.as_ptr(),
+                description.as_ptr(),
+                typ.into(),
+                group.pointer,
+            );
+            if raw.is_null() {
+                return None;
+            }
+            // Buttons are bound through the topmost properties, so hand them
+            // over before OBS takes ownership of the group.
+            let group_data = obs_properties_get_param(group.pointer) as *mut PropertiesData;
+            if !group_data.is_null() {
+                let buttons = std::mem::take(&mut (*group_data).buttons);
+                properties_data(self.pointer).buttons.extend(buttons);
+            }
+            forget(group);
+            Some(Property::new(raw, self.pointer))
+        }
+    }
+
+    /// Adds a button which calls `callback` with the state of the source (or
+    /// output) `D` when clicked. The callback returns `true` if the UI should
+    /// be refreshed.
+    ///
+    /// The button is only bound to the state when these properties are
+    /// returned from [`GetPropertiesSource::get_properties`] of `D`. Returning
+    /// them from another type logs an error, and the button does nothing.
+    ///
+    /// Returns `None` if a property called `name` already exists.
+    ///
+    /// [`GetPropertiesSource::get_properties`]: crate::source::traits::GetPropertiesSource::get_properties
+    pub fn add_button<D, F>(
+        &mut self,
+        name: ObsString,
+        text: ObsString,
+        mut callback: F,
+    ) -> Option<Property<'_>>
+    where
+        D: 'static,
+        F: FnMut(&mut Properties, &mut Property, &mut D) -> bool + 'static,
+    {
+        let mut binding = Box::new(ButtonBinding {
+            type_name: type_name::<D>(),
+            data: std::ptr::null_mut(),
+            callback: Box::new(move |props, prop, data| {
+                callback(props, prop, unsafe { &mut *(data as *mut D) })
+            }),
+        });
+        let param = &mut *binding as *mut ButtonBinding;
+        unsafe {
+            let raw = obs_properties_add_button2(
+                self.pointer,
+                name.as_ptr(),
+                text.as_ptr(),
+                Some(button_clicked),
+                param as *mut _,
+            );
+            if raw.is_null() {
+                return None;
+            }
+            properties_data(self.pointer).buttons.push(binding);
+            Some(Property::new(raw, self.pointer))
+        }
+    }
+
+    /// Binds the buttons added with [`Properties::add_button`] for `D` to
+    /// `state`. Buttons added for another type stay unbound and fail with
+    /// [`Error::ButtonTypeMismatch`].
+    ///
+    /// # Safety
+    ///
+    /// `state` must outlive these properties.
+    pub(crate) unsafe fn bind_buttons<D>(&mut self, state: &mut D) -> Result<()> {
+        let data = unsafe { properties_data(self.pointer) };
+        let mut result = Ok(());
+        for button in data.buttons.iter_mut() {
+            // `TypeId` needs `D: 'static`, which isn't required from sources.
+            if button.type_name == type_name::<D>() {
+                button.data = state as *mut D as *mut c_void;
+            } else {
+                result = Err(Error::ButtonTypeMismatch(
+                    button.type_name,
+                    type_name::<D>(),
+                ));
+            }
+        }
+        result
+    }
+
+    /// Finds a property by its settings name, including properties inside
+    /// groups.
+    pub fn get(&mut self, name: impl Into<ObsString>) -> Option<Property<'_>> {
+        let name = name.into();
+        unsafe {
+            let raw = obs_properties_get(self.pointer, name.as_ptr());
+            if raw.is_null() {
+                None
+            } else {
+                Some(Property::new(raw, self.pointer))
+            }
         }
     }
 }
 
-/// Wrapper around [`obs_property_t`], which is a list of possible values for a
-/// property.
-pub struct ListProp<'props, T> {
+type ModifiedCallback = dyn FnMut(&mut Properties, &mut Property, &mut DataObj) -> bool;
+type ButtonCallback = dyn FnMut(&mut Properties, &mut Property, *mut c_void) -> bool;
+
+/// Rust data attached to a [`obs_properties_t`] with
+/// [`obs_properties_set_param`], freed by OBS together with the properties.
+///
+/// The entries are boxed so the pointers handed to OBS stay valid when the
+/// vectors grow.
+#[derive(Default)]
+#[allow(clippy::vec_box)]
+struct PropertiesData {
+    modified: Vec<Box<Box<ModifiedCallback>>>,
+    buttons: Vec<Box<ButtonBinding>>,
+}
+
+/// A button callback together with the state it is called with, which is
+/// only known once the properties are handed to OBS.
+struct ButtonBinding {
+    type_name: &'static str,
+    data: *mut c_void,
+    callback: Box<ButtonCallback>,
+}
+
+unsafe extern "C" fn destroy_properties_data(param: *mut c_void) {
+    drop(unsafe { Box::from_raw(param as *mut PropertiesData) });
+}
+
+/// # Safety
+///
+/// `props` must be a valid pointer, whose param is either unset or a
+/// [`PropertiesData`].
+unsafe fn properties_data<'a>(props: *mut obs_properties_t) -> &'a mut PropertiesData {
+    unsafe {
+        let mut param = obs_properties_get_param(props) as *mut PropertiesData;
+        if param.is_null() {
+            param = Box::into_raw(Box::<PropertiesData>::default());
+            obs_properties_set_param(props, param as *mut _, Some(destroy_properties_data));
+        }
+        &mut *param
+    }
+}
+
+unsafe extern "C" fn modified_callback(
+    param: *mut c_void,
+    props: *mut obs_properties_t,
+    prop: *mut obs_property_t,
+    settings: *mut obs_data_t,
+) -> bool {
+    let callback = unsafe { &mut *(param as *mut Box<ModifiedCallback>) };
+    let Some(mut settings) = (unsafe { DataObj::from_raw_unchecked(settings) }) else {
+        return false;
+    };
+    // The properties are owned by OBS, so they must not be destroyed here.
+    let mut props = Properties { pointer: props };
+    let mut prop = Property::new(prop, props.pointer);
+    let refresh = callback(&mut props, &mut prop, &mut settings);
+    forget(props);
+    forget(settings);
+    refresh
+}
+
+unsafe extern "C" fn button_clicked(
+    props: *mut obs_properties_t,
+    prop: *mut obs_property_t,
+    param: *mut c_void,
+) -> bool {
+    let binding = unsafe { &mut *(param as *mut ButtonBinding) };
+    if binding.data.is_null() {
+        return false;
+    }
+    // The properties are owned by OBS, so they must not be destroyed here.
+    let mut props = Properties { pointer: props };
+    let mut prop = Property::new(prop, props.pointer);
+    let refresh = (binding.callback)(&mut props, &mut prop, binding.data);
+    forget(props);
+    refresh
+}
+
+/// Handle to a [`obs_property_t`] owned by a [`Properties`].
+pub struct Property<'props> {
     raw: *mut obs_property_t,
+    props: *mut obs_properties_t,
     _props: PhantomData<&'props mut Properties>,
-    _type: PhantomData<T>,
 }
 
-impl<T> PtrWrapper for ListProp<'_, T> {
-    type Pointer = obs_property_t;
-
-    unsafe fn from_raw_unchecked(raw: *mut Self::Pointer) -> Option<Self> {
-        if raw.is_null() {
-            None
-        } else {
-            Some(Self {
-                raw,
-                _props: PhantomData,
-                _type: PhantomData,
-            })
+impl<'props> Property<'props> {
+    fn new(raw: *mut obs_property_t, props: *mut obs_properties_t) -> Self {
+        Self {
+            raw,
+            props,
+            _props: PhantomData,
         }
     }
 
-    unsafe fn as_ptr(&self) -> *const Self::Pointer {
+    /// # Safety
+    /// Returns a pointer to the raw obs_property data which if modified could
+    /// cause UB.
+    pub unsafe fn get_raw(&self) -> *mut obs_property_t {
         self.raw
     }
 
-    unsafe fn get_ref(ptr: *mut Self::Pointer) -> *mut Self::Pointer {
-        ptr
+    /// Converts this property into a [`ListProp`], if it is a list whose
+    /// values have the format of `T`.
+    pub fn into_list<T: ListType>(self) -> Option<ListProp<'props, T>> {
+        unsafe {
+            if obs_property_get_type(self.raw) != obs_property_type_OBS_PROPERTY_LIST {
+                return None;
+            }
+            if ComboFormat::from_raw(obs_property_list_format(self.raw)).ok()? != T::format() {
+                return None;
+            }
+        }
+        Some(ListProp {
+            prop: self,
+            _type: PhantomData,
+        })
+    }
+
+    pub fn visible(&self) -> bool {
+        unsafe { obs_property_visible(self.raw) }
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        unsafe { obs_property_set_visible(self.raw, visible) }
+        self
+    }
+
+    pub fn enabled(&self) -> bool {
+        unsafe { obs_property_enabled(self.raw) }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        unsafe { obs_property_set_enabled(self.raw, enabled) }
+        self
+    }
+
+    /// Sets the text shown as a tooltip for this property.
+    pub fn set_long_description(&mut self, description: impl Into<ObsString>) -> &mut Self {
+        let description = description.into();
+        unsafe { obs_property_set_long_description(self.raw, description.as_ptr()) }
+        self
+    }
+
+    /// Sets a callback which is called when the user changes the value of this
+    /// property, replacing any previous one. The callback can change other
+    /// properties, e.g. to show or hide them, and returns `true` if the UI
+    /// should be refreshed.
+    ///
+    /// The callback is kept alive until the properties are destroyed by OBS.
+    pub fn on_modified<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnMut(&mut Properties, &mut Property, &mut DataObj) -> bool + 'static,
+    {
+        let mut callback: Box<Box<ModifiedCallback>> = Box::new(Box::new(callback));
+        let param = &mut *callback as *mut Box<ModifiedCallback>;
+        unsafe {
+            properties_data(self.props).modified.push(callback);
+            obs_property_set_modified_callback2(self.raw, Some(modified_callback), param as *mut _);
+        }
+        self
+    }
+}
+
+/// Wrapper around [`obs_property_t`], which is a list of possible values for a
+/// property.
+pub struct ListProp<'props, T> {
+    prop: Property<'props>,
+    _type: PhantomData<T>,
+}
+
+impl<'props, T> Deref for ListProp<'props, T> {
+    type Target = Property<'props>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.prop
     }
+}
 
-    unsafe fn release(_ptr: *mut Self::Pointer) {}
+impl<T> DerefMut for ListProp<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.prop
+    }
 }
 
 impl<T: ListType> ListProp<'_, T> {
     pub fn push(&mut self, name: impl Into<ObsString>, value: T) {
-        value.push_into(self.raw, name.into());
+        value.push_into(self.prop.raw, name.into());
     }
 
     pub fn insert(&mut self, index: usize, name: impl Into<ObsString>, value: T) {
-        value.insert_into(self.raw, name.into(), index);
+        value.insert_into(self.prop.raw, name.into(), index);
     }
 
     pub fn remove(&mut self, index: usize) {
         unsafe {
-            obs_property_list_item_remove(self.raw, index as size_t);
+            obs_property_list_item_remove(self.prop.raw, index as size_t);
         }
     }
 
     pub fn disable(&mut self, index: usize, disabled: bool) {
         unsafe {
-            obs_property_list_item_disable(self.raw, index as size_t, disabled);
+            obs_property_list_item_disable(self.prop.raw, index as size_t, disabled);
         }
     }
 }
@@ -249,6 +565,7 @@ pub struct NumberProp<T> {
     max: T,
     step: T,
     slider: bool,
+    suffix: Option<ObsString>,
     typ: NumberType,
 }
 
@@ -260,6 +577,7 @@ impl<T: PrimInt> NumberProp<T> {
             max: T::max_value(),
             step: one(),
             slider: false,
+            suffix: None,
             typ: NumberType::Integer,
         }
     }
@@ -273,6 +591,7 @@ impl<T: Float> NumberProp<T> {
             max: T::max_value(),
             step,
             slider: false,
+            suffix: None,
             typ: NumberType::Float,
         }
     }
@@ -307,15 +626,26 @@ impl<T: Num + Bounded + Copy> NumberProp<T> {
         self.slider = true;
         self
     }
+    /// Sets a suffix shown after the value, e.g. a unit like `" px"`.
+    pub fn with_suffix(mut self, suffix: impl Into<ObsString>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
 }
 
 pub trait ObsProp {
-    /// Callback to add this property to a [`obs_properties_t`].
+    /// Callback to add this property to a [`obs_properties_t`], returning the
+    /// created property.
     ///
     /// # Safety
     ///
     /// Must call with a valid pointer.
-    unsafe fn add_to_props(self, p: *mut obs_properties_t, name: ObsString, description: ObsString);
+    unsafe fn add_to_props(
+        self,
+        p: *mut obs_properties_t,
+        name: ObsString,
+        description: ObsString,
+    ) -> *mut obs_property_t;
 }
 
 impl<T: ToPrimitive> ObsProp for NumberProp<T> {
@@ -324,14 +654,14 @@ impl<T: ToPrimitive> ObsProp for NumberProp<T> {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
+    ) -> *mut obs_property_t {
         match self.typ {
             NumberType::Integer => {
                 let min: c_int = NumCast::from(self.min).unwrap();
                 let max: c_int = NumCast::from(self.max).unwrap();
                 let step: c_int = NumCast::from(self.step).unwrap();
 
-                if self.slider {
+                let prop = if self.slider {
                     obs_properties_add_int_slider(
                         p,
                         name.as_ptr(),
@@ -339,17 +669,21 @@ impl<T: ToPrimitive> ObsProp for NumberProp<T> {
                         min,
                         max,
                         step,
-                    );
+                    )
                 } else {
-                    obs_properties_add_int(p, name.as_ptr(), description.as_ptr(), min, max, step);
+                    obs_properties_add_int(p, name.as_ptr(), description.as_ptr(), min, max, step)
+                };
+                if let Some(suffix) = &self.suffix {
+                    unsafe { obs_property_int_set_suffix(prop, suffix.as_ptr()) }
                 }
+                prop
             }
             NumberType::Float => {
                 let min: f64 = NumCast::from(self.min).unwrap();
                 let max: f64 = NumCast::from(self.max).unwrap();
                 let step: f64 = NumCast::from(self.step).unwrap();
 
-                if self.slider {
+                let prop = if self.slider {
                     obs_properties_add_float_slider(
                         p,
                         name.as_ptr(),
@@ -357,17 +691,14 @@ impl<T: ToPrimitive> ObsProp for NumberProp<T> {
                         min,
                         max,
                         step,
-                    );
+                    )
                 } else {
-                    obs_properties_add_float(
-                        p,
-                        name.as_ptr(),
-                        description.as_ptr(),
-                        min,
-                        max,
-                        step,
-                    );
+                    obs_properties_add_float(p, name.as_ptr(), description.as_ptr(), min, max, step)
+                };
+                if let Some(suffix) = &self.suffix {
+                    unsafe { obs_property_float_set_suffix(prop, suffix.as_ptr()) }
                 }
+                prop
             }
         }
     }
@@ -381,17 +712,41 @@ impl ObsProp for BoolProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
-        obs_properties_add_bool(p, name.as_ptr(), description.as_ptr());
+    ) -> *mut obs_property_t {
+        obs_properties_add_bool(p, name.as_ptr(), description.as_ptr())
     }
 }
 pub struct TextProp {
     typ: TextType,
+    info_type: Option<TextInfoType>,
+    word_wrap: Option<bool>,
 }
 
 impl TextProp {
     pub fn new(typ: TextType) -> Self {
-        Self { typ }
+        Self {
+            typ,
+            info_type: None,
+            word_wrap: None,
+        }
+    }
+
+    /// Creates a read-only [`TextType::Info`] property, shown as a normal,
+    /// warning or error message.
+    pub fn info(info_type: TextInfoType) -> Self {
+        Self::new(TextType::Info).with_info_type(info_type)
+    }
+
+    /// Sets how an info text is shown. Only used by [`TextType::Info`].
+    pub fn with_info_type(mut self, info_type: TextInfoType) -> Self {
+        self.info_type = Some(info_type);
+        self
+    }
+
+    /// Sets whether an info text wraps. Only used by [`TextType::Info`].
+    pub fn with_word_wrap(mut self, word_wrap: bool) -> Self {
+        self.word_wrap = Some(word_wrap);
+        self
     }
 }
 
@@ -401,8 +756,17 @@ impl ObsProp for TextProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
-        obs_properties_add_text(p, name.as_ptr(), description.as_ptr(), self.typ.into());
+    ) -> *mut obs_property_t {
+        let prop = obs_properties_add_text(p, name.as_ptr(), description.as_ptr(), self.typ.into());
+        unsafe {
+            if let Some(info_type) = self.info_type {
+                obs_property_text_set_info_type(prop, info_type.into());
+            }
+            if let Some(word_wrap) = self.word_wrap {
+                obs_property_text_set_info_word_wrap(prop, word_wrap);
+            }
+        }
+        prop
     }
 }
 
@@ -414,8 +778,76 @@ impl ObsProp for ColorProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
-        obs_properties_add_color(p, name.as_ptr(), description.as_ptr());
+    ) -> *mut obs_property_t {
+        obs_properties_add_color(p, name.as_ptr(), description.as_ptr())
+    }
+}
+
+/// Adds a color property which also lets the user pick the alpha channel.
+pub struct ColorAlphaProp;
+
+impl ObsProp for ColorAlphaProp {
+    unsafe fn add_to_props(
+        self,
+        p: *mut obs_properties_t,
+        name: ObsString,
+        description: ObsString,
+    ) -> *mut obs_property_t {
+        unsafe { obs_properties_add_color_alpha(p, name.as_ptr(), description.as_ptr()) }
+    }
+}
+
+/// Adds a frame rate property, which lets the user pick one of the named
+/// options or a frame rate within one of the ranges.
+#[derive(Default)]
+pub struct FrameRateProp {
+    options: Vec<(ObsString, ObsString)>,
+    ranges: Vec<(media_frames_per_second, media_frames_per_second)>,
+}
+
+impl FrameRateProp {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a named option, e.g. to follow the frame rate of the output.
+    pub fn with_option(
+        mut self,
+        name: impl Into<ObsString>,
+        description: impl Into<ObsString>,
+    ) -> Self {
+        self.options.push((name.into(), description.into()));
+        self
+    }
+
+    /// Adds a range of frame rates, each given as `(numerator, denominator)`.
+    pub fn with_fps_range(mut self, min: (u32, u32), max: (u32, u32)) -> Self {
+        let fps = |(numerator, denominator)| media_frames_per_second {
+            numerator,
+            denominator,
+        };
+        self.ranges.push((fps(min), fps(max)));
+        self
+    }
+}
+
+impl ObsProp for FrameRateProp {
+    unsafe fn add_to_props(
+        self,
+        p: *mut obs_properties_t,
+        name: ObsString,
+        description: ObsString,
+    ) -> *mut obs_property_t {
+        unsafe {
+            let prop = obs_properties_add_frame_rate(p, name.as_ptr(), description.as_ptr());
+            for (name, description) in &self.options {
+                obs_property_frame_rate_option_add(prop, name.as_ptr(), description.as_ptr());
+            }
+            for (min, max) in self.ranges {
+                obs_property_frame_rate_fps_range_add(prop, min, max);
+            }
+            prop
+        }
     }
 }
 
@@ -434,8 +866,8 @@ impl ObsProp for FontProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
-        obs_properties_add_font(p, name.as_ptr(), description.as_ptr());
+    ) -> *mut obs_property_t {
+        obs_properties_add_font(p, name.as_ptr(), description.as_ptr())
     }
 }
 
@@ -486,7 +918,7 @@ impl ObsProp for PathProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
+    ) -> *mut obs_property_t {
         obs_properties_add_path(
             p,
             name.as_ptr(),
@@ -494,7 +926,7 @@ impl ObsProp for PathProp {
             self.typ.into(),
             ObsString::ptr_or_null(&self.filter),
             ObsString::ptr_or_null(&self.default_path),
-        );
+        )
     }
 }
 
@@ -530,7 +962,7 @@ impl ObsProp for EditableListProp {
         p: *mut obs_properties_t,
         name: ObsString,
         description: ObsString,
-    ) {
+    ) -> *mut obs_property_t {
         obs_properties_add_editable_list(
             p,
             name.as_ptr(),
@@ -538,6 +970,6 @@ impl ObsProp for EditableListProp {
             self.typ.into(),
             ObsString::ptr_or_null(&self.filter),
             ObsString::ptr_or_null(&self.default_path),
-        );
+        )
     }
 }
@@ -20,6 +20,44 @@ pub enum Error {
     /// Error converting path to str
     #[error("Path Error: utf8")]
     PathUtf8,
+    /// Unknown `obs_data_type` of a data item
+    #[error("Unknown Data Type: {0}")]
+    UnknownDataType(i64),
+    /// Unknown `obs_data_number_type` of a numeric data item
+    #[error("Unknown Number Type: {0}")]
+    UnknownNumberType(i64),
+    /// Operation not supported by OBS
+    #[error("Unsupported: {0}")]
+    Unsupported(&'static str),
+    /// Failed to write a JSON file
+    #[error("Failed to save JSON: {0}")]
+    SaveJson(String),
+    /// Stored value has a different type than requested
+    #[error("Data Type Mismatch: `{0}` expected {1}, found {2:?}")]
+    DataTypeMismatch(String, &'static str, crate::data::DataType),
+    /// Button added for a different type than the properties are returned from
+    #[error("Button Type Mismatch: added for `{0}`, returned from `{1}`")]
+    ButtonTypeMismatch(&'static str, &'static str),
+    /// Audio samples which don't match the speaker layout
+    #[error("Audio Layout Mismatch: {0}")]
+    AudioLayoutMismatch(&'static str),
+    /// Error from (de)serializing settings
+    #[error("Serde Error: {0}")]
+    Serde(String),
+}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Serde(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self::Serde(msg.to_string())
+    }
 }
 
 pub trait OptionExt {
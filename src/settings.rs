@@ -0,0 +1,44 @@
+use crate::{data::DataObj, properties::Properties, Result};
+
+#[cfg(feature = "derive")]
+pub use obs_wrapper_derive::ObsSettings;
+
+/// Settings of a source or output described by a single struct, so the
+/// properties, the defaults and the parsing use the same keys.
+///
+/// Usually implemented with `#[derive(ObsSettings)]`, which needs the
+/// `derive` feature:
+///
+/// ```ignore
+/// impl GetPropertiesSource for MySource {
+///     fn get_properties(&mut self) -> Properties {
+///         Settings::properties()
+///     }
+/// }
+///
+/// impl GetDefaultsSource for MySource {
+///     fn get_defaults(settings: &mut DataObj) {
+///         if let Err(err) = Settings::set_defaults(settings) {
+///             log::error!("failed to set defaults: {}", err);
+///         }
+///     }
+/// }
+///
+/// impl UpdateSource for MySource {
+///     fn update(&mut self, settings: &mut DataObj, _context: &mut GlobalContext) {
+///         self.settings = Settings::from_settings(settings);
+///     }
+/// }
+/// ```
+pub trait ObsSettings: Sized {
+    /// Creates the properties shown to the user.
+    fn properties() -> Properties;
+
+    /// Sets the default value of every setting. Fails if a text default
+    /// contains a nul byte.
+    fn set_defaults(settings: &mut DataObj) -> Result<()>;
+
+    /// Reads the settings, using the default of any setting which is missing
+    /// or has the wrong type.
+    fn from_settings(settings: &DataObj) -> Self;
+}
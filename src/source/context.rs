@@ -1,8 +1,14 @@
+use super::traits::TypeDataSource;
+use crate::graphics::ColorSpace;
 use crate::hotkey::{Hotkey, HotkeyCallbacks};
 use crate::media::audio::AudioRef;
 use crate::prelude::DataObj;
 use crate::string::ObsString;
-use obs_sys::obs_get_audio;
+use obs_sys::{
+    gs_get_color_space, gs_get_linear_srgb, gs_set_linear_srgb, obs_get_audio,
+    obs_get_video_hdr_nominal_peak_level, obs_get_video_sdr_white_level,
+};
+use std::ffi::c_void;
 
 pub struct GlobalContext;
 pub struct VideoRenderContext;
@@ -14,6 +20,35 @@ impl GlobalContext {
     }
 }
 
+impl VideoRenderContext {
+    /// The color space of the current render target.
+    pub fn color_space(&self) -> ColorSpace {
+        let raw = unsafe { gs_get_color_space() };
+        ColorSpace::from_raw(raw).unwrap_or(ColorSpace::SRGB)
+    }
+
+    /// Nits of SDR white, to scale SDR colors when rendering in
+    /// [`ColorSpace::Rec709ScRGB`].
+    pub fn sdr_white_level(&self) -> f32 {
+        unsafe { obs_get_video_sdr_white_level() }
+    }
+
+    /// Nits of HDR peak white.
+    pub fn hdr_nominal_peak_level(&self) -> f32 {
+        unsafe { obs_get_video_hdr_nominal_peak_level() }
+    }
+
+    /// Whether sRGB textures are sampled and rendered as linear colors.
+    pub fn linear_srgb(&self) -> bool {
+        unsafe { gs_get_linear_srgb() }
+    }
+
+    /// Returns the previous value, which should be restored after drawing.
+    pub fn set_linear_srgb(&mut self, linear_srgb: bool) -> bool {
+        unsafe { gs_set_linear_srgb(linear_srgb) }
+    }
+}
+
 impl Default for VideoRenderContext {
     fn default() -> Self {
         Self
@@ -30,14 +65,20 @@ pub struct CreatableSourceContext<'a, D> {
     pub(crate) hotkey_callbacks: HotkeyCallbacks<D>,
     pub settings: DataObj<'a>,
     pub global: &'a mut GlobalContext,
+    type_data: *mut c_void,
 }
 
 impl<'a, D> CreatableSourceContext<'a, D> {
-    pub(crate) unsafe fn from_raw(settings: DataObj<'a>, global: &'a mut GlobalContext) -> Self {
+    pub(crate) unsafe fn from_raw(
+        settings: DataObj<'a>,
+        type_data: *mut c_void,
+        global: &'a mut GlobalContext,
+    ) -> Self {
         Self {
             hotkey_callbacks: Default::default(),
             settings,
             global,
+            type_data,
         }
     }
 
@@ -56,3 +97,15 @@ impl<'a, D> CreatableSourceContext<'a, D> {
         self.global.with_audio(func)
     }
 }
+
+impl<D: TypeDataSource> CreatableSourceContext<'_, D> {
+    /// The data attached with [`SourceInfoBuilder::with_type_data`], if any.
+    /// It is only borrowed during creation, so share it with an `Arc` to keep
+    /// it in the source.
+    ///
+    /// [`SourceInfoBuilder::with_type_data`]: super::SourceInfoBuilder::with_type_data
+    pub fn type_data(&self) -> Option<&D::TypeData> {
+        // Only `with_type_data` of the builder of `D` sets the type data.
+        unsafe { super::ffi::type_data::<D>(self.type_data) }
+    }
+}
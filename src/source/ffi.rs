@@ -1,28 +1,55 @@
 use super::context::{CreatableSourceContext, GlobalContext, VideoRenderContext};
+use super::transition::TransitionRenderContext;
 use super::{traits::*, SourceRef};
 use super::{EnumActiveContext, EnumAllContext};
-use crate::media::{audio::AudioDataContext, video::VideoDataSourceContext};
+use crate::graphics::ColorSpace;
+use crate::media::{
+    audio::{AudioDataContext, AudioMixContext, AudioRenderContext},
+    video::VideoDataSourceContext,
+};
 use crate::{
     data::DataObj,
     hotkey::{Hotkey, HotkeyCallbacks},
     wrapper::PtrWrapper,
 };
 use paste::item;
+use std::any::Any;
 use std::collections::HashMap;
 use std::convert::TryFrom;
-use std::ffi::c_void;
+use std::ffi::{c_void, CStr};
 use std::mem::forget;
 use std::os::raw::c_char;
 
 use obs_sys::{
-    gs_effect_t, obs_audio_data, obs_button_type, obs_data_t, obs_hotkey_id,
-    obs_hotkey_register_source, obs_hotkey_t, obs_key_event, obs_media_state, obs_mouse_event,
-    obs_properties, obs_source_audio_mix, obs_source_enum_proc_t, obs_source_frame, obs_source_t,
-    size_t,
+    audio_output_data, gs_color_space, gs_effect_t, obs_audio_data, obs_button_type, obs_data_t,
+    obs_hotkey_id, obs_hotkey_register_source, obs_hotkey_t, obs_key_event, obs_media_state,
+    obs_missing_file_create, obs_missing_file_src_OBS_MISSING_FILE_SOURCE,
+    obs_missing_files_add_file, obs_missing_files_create, obs_missing_files_t, obs_mouse_event,
+    obs_obj_get_data, obs_properties, obs_source_audio_mix, obs_source_enum_proc_t,
+    obs_source_frame, obs_source_get_type_data, obs_source_t, obs_transition_audio_render, size_t,
 };
 
+/// How the type data is stored, so it can be checked with a downcast before
+/// being borrowed from a [`SourceRef`].
+pub(crate) type TypeDataBox = Box<dyn Any + Send + Sync>;
+
+/// Borrows the type data stored by [`SourceInfoBuilder::with_type_data`].
+///
+/// # Safety
+///
+/// `type_data` must be NULL or come from `with_type_data`.
+///
+/// [`SourceInfoBuilder::with_type_data`]: super::SourceInfoBuilder::with_type_data
+pub(crate) unsafe fn type_data<'a, D: TypeDataSource>(
+    type_data: *mut c_void,
+) -> Option<&'a D::TypeData> {
+    unsafe { (type_data as *const TypeDataBox).as_ref() }?.downcast_ref()
+}
+
 struct DataWrapper<D> {
     data: D,
+    /// The source owning this data, not referenced to avoid a cycle.
+    source: *mut obs_source_t,
     #[allow(clippy::type_complexity)]
     hotkey_callbacks: HashMap<obs_hotkey_id, Box<dyn FnMut(&mut Hotkey, &mut D)>>,
 }
@@ -48,10 +75,11 @@ impl<D> DataWrapper<D> {
     }
 }
 
-impl<D> From<D> for DataWrapper<D> {
-    fn from(data: D) -> Self {
+impl<D> DataWrapper<D> {
+    fn new(data: D, source: *mut obs_source_t) -> Self {
         Self {
             data,
+            source,
             hotkey_callbacks: HashMap::new(),
         }
     }
@@ -80,6 +108,8 @@ impl_simple_fn!(
 
     activate => ActivateSource
     deactivate => DeactivateSource
+    show => ShowSource
+    hide => HideSource
 );
 
 pub unsafe extern "C" fn create<D: Sourceable>(
@@ -89,12 +119,13 @@ pub unsafe extern "C" fn create<D: Sourceable>(
     let mut global = GlobalContext;
     // this is later forgotten
     let settings = DataObj::from_raw_unchecked(settings).unwrap();
-    let mut context = CreatableSourceContext::from_raw(settings, &mut global);
+    let type_data = unsafe { obs_source_get_type_data(source) };
+    let mut context = CreatableSourceContext::from_raw(settings, type_data, &mut global);
     let source_context = SourceRef::from_raw(source).expect("create");
 
     let data = D::create(&mut context, source_context);
 
-    let wrapper = DataWrapper::from(data);
+    let wrapper = DataWrapper::new(data, source);
     forget(context.settings);
     let callbacks = context.hotkey_callbacks;
 
@@ -121,6 +152,20 @@ pub unsafe extern "C" fn update<D: UpdateSource>(data: *mut c_void, settings: *m
     forget(settings);
 }
 
+pub unsafe extern "C" fn save<D: SaveSource>(data: *mut c_void, settings: *mut obs_data_t) {
+    let data: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let mut settings = unsafe { DataObj::from_raw_unchecked(settings) }.unwrap();
+    D::save(&mut data.data, &mut settings);
+    forget(settings);
+}
+
+pub unsafe extern "C" fn load<D: LoadSource>(data: *mut c_void, settings: *mut obs_data_t) {
+    let data: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let mut settings = unsafe { DataObj::from_raw_unchecked(settings) }.unwrap();
+    D::load(&mut data.data, &mut settings);
+    forget(settings);
+}
+
 pub unsafe extern "C" fn video_render<D: VideoRenderSource>(
     data: *mut std::os::raw::c_void,
     _effect: *mut gs_effect_t,
@@ -131,46 +176,86 @@ pub unsafe extern "C" fn video_render<D: VideoRenderSource>(
     D::video_render(&mut wrapper.data, &mut global, &mut render);
 }
 
+pub unsafe extern "C" fn video_get_color_space<D: VideoColorSpaceSource>(
+    data: *mut std::os::raw::c_void,
+    count: size_t,
+    preferred_spaces: *const gs_color_space,
+) -> gs_color_space {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let preferred: Vec<ColorSpace> = if preferred_spaces.is_null() {
+        vec![]
+    } else {
+        unsafe { std::slice::from_raw_parts(preferred_spaces, count) }
+            .iter()
+            .filter_map(|&space| ColorSpace::from_raw(space).ok())
+            .collect()
+    };
+    D::video_get_color_space(&mut wrapper.data, &preferred).as_raw()
+}
+
 pub unsafe extern "C" fn audio_render<D: AudioRenderSource>(
     data: *mut std::os::raw::c_void,
-    _ts_out: *mut u64,
-    _audio_output: *mut obs_source_audio_mix,
-    _mixers: u32,
-    _channels: size_t,
-    _sample_rate: size_t,
+    ts_out: *mut u64,
+    audio_output: *mut obs_source_audio_mix,
+    mixers: u32,
+    channels: size_t,
+    sample_rate: size_t,
 ) -> bool {
-    let wrapper: &mut DataWrapper<D> = &mut *(data as *mut DataWrapper<D>);
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
     let mut global = GlobalContext;
-    D::audio_render(&mut wrapper.data, &mut global);
-    // TODO: understand what this bool is
-    true
+    let mut audio = unsafe {
+        AudioRenderContext::new(
+            &mut *audio_output,
+            &mut *ts_out,
+            mixers,
+            channels,
+            sample_rate,
+        )
+    };
+    D::audio_render(&mut wrapper.data, &mut global, &mut audio)
+}
+
+pub unsafe extern "C" fn audio_mix<D: AudioMixSource>(
+    data: *mut std::os::raw::c_void,
+    ts_out: *mut u64,
+    audio_output: *mut audio_output_data,
+    channels: size_t,
+    sample_rate: size_t,
+) -> bool {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let mut audio =
+        unsafe { AudioMixContext::new(&mut *audio_output, &mut *ts_out, channels, sample_rate) };
+    D::audio_mix(&mut wrapper.data, &mut audio)
 }
 
 pub unsafe extern "C" fn get_properties<D: GetPropertiesSource>(
     data: *mut std::os::raw::c_void,
 ) -> *mut obs_properties {
     let wrapper: &mut DataWrapper<D> = &mut *(data as *mut DataWrapper<D>);
-    let properties = D::get_properties(&mut wrapper.data);
+    let mut properties = D::get_properties(&mut wrapper.data);
+    if let Err(err) = unsafe { properties.bind_buttons(&mut wrapper.data) } {
+        log::error!("{}", err);
+    }
     properties.into_raw()
 }
 
 pub unsafe extern "C" fn enum_active_sources<D: EnumActiveSource>(
     data: *mut std::os::raw::c_void,
-    _enum_callback: obs_source_enum_proc_t,
-    _param: *mut std::os::raw::c_void,
+    enum_callback: obs_source_enum_proc_t,
+    param: *mut std::os::raw::c_void,
 ) {
-    let wrapper: &mut DataWrapper<D> = &mut *(data as *mut DataWrapper<D>);
-    let context = EnumActiveContext {};
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let context = EnumActiveContext::new(wrapper.source, enum_callback, param);
     D::enum_active_sources(&mut wrapper.data, &context);
 }
 
 pub unsafe extern "C" fn enum_all_sources<D: EnumAllSource>(
     data: *mut std::os::raw::c_void,
-    _enum_callback: obs_source_enum_proc_t,
-    _param: *mut std::os::raw::c_void,
+    enum_callback: obs_source_enum_proc_t,
+    param: *mut std::os::raw::c_void,
 ) {
-    let wrapper: &mut DataWrapper<D> = &mut *(data as *mut DataWrapper<D>);
-    let context = EnumAllContext {};
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let context = EnumAllContext::new(wrapper.source, enum_callback, param);
     D::enum_all_sources(&mut wrapper.data, &context);
 }
 
@@ -179,6 +264,99 @@ impl_simple_fn!(
     transition_stop => TransitionStopSource
 );
 
+pub unsafe extern "C" fn transition_video_render<D: TransitionSource>(
+    data: *mut std::os::raw::c_void,
+    _effect: *mut gs_effect_t,
+) {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let mut global = GlobalContext;
+    let mut transition = TransitionRenderContext::new(wrapper.source);
+    D::transition_render(&mut wrapper.data, &mut global, &mut transition);
+}
+
+pub unsafe extern "C" fn transition_audio_render<D: TransitionSource>(
+    data: *mut std::os::raw::c_void,
+    ts_out: *mut u64,
+    audio_output: *mut obs_source_audio_mix,
+    mixers: u32,
+    channels: size_t,
+    sample_rate: size_t,
+) -> bool {
+    let source = unsafe { (*(data as *mut DataWrapper<D>)).source };
+    unsafe {
+        obs_transition_audio_render(
+            source,
+            ts_out,
+            audio_output,
+            mixers,
+            channels,
+            sample_rate,
+            Some(transition_audio_mix_a::<D>),
+            Some(transition_audio_mix_b::<D>),
+        )
+    }
+}
+
+unsafe extern "C" fn transition_audio_mix_a<D: TransitionSource>(
+    data: *mut std::os::raw::c_void,
+    t: f32,
+) -> f32 {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    D::audio_mix_a(&mut wrapper.data, t)
+}
+
+unsafe extern "C" fn transition_audio_mix_b<D: TransitionSource>(
+    data: *mut std::os::raw::c_void,
+    t: f32,
+) -> f32 {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    D::audio_mix_b(&mut wrapper.data, t)
+}
+
+pub unsafe extern "C" fn missing_files<D: MissingFilesSource>(
+    data: *mut std::os::raw::c_void,
+) -> *mut obs_missing_files_t {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    let files = unsafe { obs_missing_files_create() };
+    for file in D::missing_files(&mut wrapper.data) {
+        // The replace fn is passed as the data of the callback, so nothing
+        // has to be freed with the file.
+        let replace = file.replace as *const () as *mut c_void;
+        unsafe {
+            let file = obs_missing_file_create(
+                file.path.as_ptr(),
+                Some(missing_file_replace::<D>),
+                obs_missing_file_src_OBS_MISSING_FILE_SOURCE as _,
+                wrapper.source as *mut c_void,
+                replace,
+            );
+            obs_missing_files_add_file(files, file);
+        }
+    }
+    files
+}
+
+/// Called by OBS as `callback(src, new_path, data)`: `src` is the source
+/// given to `obs_missing_file_create`, not its data, and `data` is the
+/// replace fn.
+unsafe extern "C" fn missing_file_replace<D: MissingFilesSource>(
+    src: *mut std::os::raw::c_void,
+    new_path: *const c_char,
+    replace: *mut std::os::raw::c_void,
+) {
+    let data = unsafe { obs_obj_get_data(src) };
+    let Some(wrapper) = (unsafe { (data as *mut DataWrapper<D>).as_mut() }) else {
+        return;
+    };
+    let replace: fn(&mut D, &str) = unsafe { std::mem::transmute(replace) };
+    let new_path = if new_path.is_null() {
+        Default::default()
+    } else {
+        unsafe { CStr::from_ptr(new_path) }.to_string_lossy()
+    };
+    replace(&mut wrapper.data, &new_path);
+}
+
 pub unsafe extern "C" fn video_tick<D: VideoTickSource>(
     data: *mut std::os::raw::c_void,
     seconds: f32,
@@ -207,6 +385,61 @@ pub unsafe extern "C" fn filter_video<D: FilterVideoSource>(
     video
 }
 
+pub unsafe extern "C" fn filter_add<D: FilterAddSource>(
+    data: *mut c_void,
+    source: *mut obs_source_t,
+) {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    // `parent` is alive while the filter is added, but don't panic across
+    // FFI if it can't be referenced.
+    if let Some(parent) = SourceRef::from_raw(source) {
+        D::filter_add(&mut wrapper.data, parent);
+    }
+}
+
+pub unsafe extern "C" fn filter_remove<D: FilterRemoveSource>(
+    data: *mut c_void,
+    source: *mut obs_source_t,
+) {
+    let wrapper: &mut DataWrapper<D> = unsafe { &mut *(data as *mut DataWrapper<D>) };
+    // NULL when the parent is being destroyed, see `FilterRemoveSource`.
+    let parent = SourceRef::from_raw(source);
+    D::filter_remove(&mut wrapper.data, parent);
+}
+
+pub unsafe extern "C" fn get_defaults2<D: GetDefaults2Source>(
+    type_data: *mut c_void,
+    settings: *mut obs_data_t,
+) {
+    let type_data = unsafe { self::type_data::<D>(type_data) }.expect("get_defaults2");
+    // this is later forgotten
+    let mut settings = unsafe { DataObj::from_raw_unchecked(settings) }.unwrap();
+    D::get_defaults2(type_data, &mut settings);
+    forget(settings);
+}
+
+pub unsafe extern "C" fn get_properties2<D: GetProperties2Source>(
+    data: *mut c_void,
+    type_data: *mut c_void,
+) -> *mut obs_properties {
+    let type_data = unsafe { self::type_data::<D>(type_data) }.expect("get_properties2");
+    let wrapper = unsafe { (data as *mut DataWrapper<D>).as_mut() };
+    match wrapper {
+        Some(wrapper) => {
+            let mut properties = D::get_properties2(Some(&mut wrapper.data), type_data);
+            if let Err(err) = unsafe { properties.bind_buttons(&mut wrapper.data) } {
+                log::error!("{}", err);
+            }
+            properties.into_raw()
+        }
+        None => D::get_properties2(None, type_data).into_raw(),
+    }
+}
+
+pub unsafe extern "C" fn free_type_data(type_data: *mut c_void) {
+    drop(unsafe { Box::from_raw(type_data as *mut TypeDataBox) });
+}
+
 pub unsafe extern "C" fn media_play_pause<D: MediaPlayPauseSource>(
     data: *mut std::os::raw::c_void,
     pause: bool,
@@ -4,51 +4,69 @@ pub mod context;
 mod ffi;
 pub mod scene;
 pub mod traits;
+pub mod transition;
 
 use crate::{
-    media::state::MediaState,
+    media::{audio::SourceAudio, state::MediaState, video::SourceFrame},
     string::{DisplayExt as _, TryIntoObsString},
-    Result,
+    Error, Result,
 };
 
 pub use context::*;
 pub use traits::*;
+pub use transition::*;
 
 use obs_sys::{
-    obs_filter_get_target, obs_icon_type, obs_icon_type_OBS_ICON_TYPE_AUDIO_INPUT,
-    obs_icon_type_OBS_ICON_TYPE_AUDIO_OUTPUT, obs_icon_type_OBS_ICON_TYPE_BROWSER,
-    obs_icon_type_OBS_ICON_TYPE_CAMERA, obs_icon_type_OBS_ICON_TYPE_COLOR,
-    obs_icon_type_OBS_ICON_TYPE_CUSTOM, obs_icon_type_OBS_ICON_TYPE_DESKTOP_CAPTURE,
-    obs_icon_type_OBS_ICON_TYPE_GAME_CAPTURE, obs_icon_type_OBS_ICON_TYPE_IMAGE,
-    obs_icon_type_OBS_ICON_TYPE_MEDIA, obs_icon_type_OBS_ICON_TYPE_SLIDESHOW,
-    obs_icon_type_OBS_ICON_TYPE_TEXT, obs_icon_type_OBS_ICON_TYPE_UNKNOWN,
-    obs_icon_type_OBS_ICON_TYPE_WINDOW_CAPTURE, obs_mouse_button_type,
-    obs_mouse_button_type_MOUSE_LEFT, obs_mouse_button_type_MOUSE_MIDDLE,
-    obs_mouse_button_type_MOUSE_RIGHT, obs_source_active, obs_source_enabled,
-    obs_source_get_base_height, obs_source_get_base_width, obs_source_get_height,
+    gs_color_space, obs_enum_sources, obs_filter_get_parent, obs_filter_get_target,
+    obs_get_source_by_name, obs_get_source_by_uuid, obs_icon_type,
+    obs_icon_type_OBS_ICON_TYPE_AUDIO_INPUT, obs_icon_type_OBS_ICON_TYPE_AUDIO_OUTPUT,
+    obs_icon_type_OBS_ICON_TYPE_BROWSER, obs_icon_type_OBS_ICON_TYPE_CAMERA,
+    obs_icon_type_OBS_ICON_TYPE_COLOR, obs_icon_type_OBS_ICON_TYPE_CUSTOM,
+    obs_icon_type_OBS_ICON_TYPE_DESKTOP_CAPTURE, obs_icon_type_OBS_ICON_TYPE_GAME_CAPTURE,
+    obs_icon_type_OBS_ICON_TYPE_IMAGE, obs_icon_type_OBS_ICON_TYPE_MEDIA,
+    obs_icon_type_OBS_ICON_TYPE_SLIDESHOW, obs_icon_type_OBS_ICON_TYPE_TEXT,
+    obs_icon_type_OBS_ICON_TYPE_UNKNOWN, obs_icon_type_OBS_ICON_TYPE_WINDOW_CAPTURE,
+    obs_mouse_button_type, obs_mouse_button_type_MOUSE_LEFT, obs_mouse_button_type_MOUSE_MIDDLE,
+    obs_mouse_button_type_MOUSE_RIGHT, obs_order_movement,
+    obs_order_movement_OBS_ORDER_MOVE_BOTTOM, obs_order_movement_OBS_ORDER_MOVE_DOWN,
+    obs_order_movement_OBS_ORDER_MOVE_TOP, obs_order_movement_OBS_ORDER_MOVE_UP, obs_source_active,
+    obs_source_create, obs_source_create_private, obs_source_enabled, obs_source_enum_filters,
+    obs_source_enum_proc_t, obs_source_filter_add, obs_source_filter_count,
+    obs_source_filter_get_index, obs_source_filter_remove, obs_source_filter_set_index,
+    obs_source_filter_set_order, obs_source_get_base_height, obs_source_get_base_width,
+    obs_source_get_color_space, obs_source_get_filter_by_name, obs_source_get_height,
     obs_source_get_id, obs_source_get_name, obs_source_get_ref, obs_source_get_type,
-    obs_source_get_width, obs_source_info, obs_source_media_ended, obs_source_media_get_duration,
-    obs_source_media_get_state, obs_source_media_get_time, obs_source_media_next,
-    obs_source_media_play_pause, obs_source_media_previous, obs_source_media_restart,
-    obs_source_media_set_time, obs_source_media_started, obs_source_media_stop,
+    obs_source_get_type_data, obs_source_get_unversioned_id, obs_source_get_uuid,
+    obs_source_get_weak_source, obs_source_get_width, obs_source_info, obs_source_media_ended,
+    obs_source_media_get_duration, obs_source_media_get_state, obs_source_media_get_time,
+    obs_source_media_next, obs_source_media_play_pause, obs_source_media_previous,
+    obs_source_media_restart, obs_source_media_set_time, obs_source_media_started,
+    obs_source_media_stop, obs_source_output_audio, obs_source_output_video2,
     obs_source_process_filter_begin, obs_source_process_filter_end,
     obs_source_process_filter_tech_end, obs_source_release, obs_source_set_enabled,
     obs_source_set_name, obs_source_showing, obs_source_skip_video_filter, obs_source_t,
     obs_source_type, obs_source_type_OBS_SOURCE_TYPE_FILTER, obs_source_type_OBS_SOURCE_TYPE_INPUT,
     obs_source_type_OBS_SOURCE_TYPE_SCENE, obs_source_type_OBS_SOURCE_TYPE_TRANSITION,
-    obs_source_update, OBS_SOURCE_AUDIO, OBS_SOURCE_CONTROLLABLE_MEDIA, OBS_SOURCE_INTERACTION,
-    OBS_SOURCE_VIDEO,
+    obs_source_update, obs_transition_enable_fixed, obs_transition_fixed, obs_transition_get_time,
+    obs_weak_source_addref, obs_weak_source_expired, obs_weak_source_get_source,
+    obs_weak_source_references_source, obs_weak_source_release, obs_weak_source_t,
+    OBS_SOURCE_ASYNC_VIDEO, OBS_SOURCE_AUDIO, OBS_SOURCE_CAP_OBSOLETE, OBS_SOURCE_COMPOSITE,
+    OBS_SOURCE_CONTROLLABLE_MEDIA, OBS_SOURCE_INTERACTION, OBS_SOURCE_SUBMIX, OBS_SOURCE_VIDEO,
 };
 
 use super::{
     graphics::{
-        GraphicsAllowDirectRendering, GraphicsColorFormat, GraphicsEffect, GraphicsEffectContext,
+        ColorSpace, GraphicsAllowDirectRendering, GraphicsColorFormat, GraphicsEffect,
+        GraphicsEffectContext,
     },
     string::ObsString,
 };
 use crate::{data::DataObj, native_enum, wrapper::PtrWrapper};
 
-use std::{ffi::CString, marker::PhantomData};
+use std::{
+    ffi::{c_void, CStr, CString},
+    marker::PhantomData,
+};
 
 native_enum!(MouseButton, obs_mouse_button_type {
     Left => MOUSE_LEFT,
@@ -84,6 +102,15 @@ SourceType, obs_source_type {
     Transition => OBS_SOURCE_TYPE_TRANSITION,
 });
 
+native_enum!(
+/// Where to move a filter, see [`SourceRef::set_filter_order`]
+OrderMovement, obs_order_movement {
+    Up => OBS_ORDER_MOVE_UP,
+    Down => OBS_ORDER_MOVE_DOWN,
+    Top => OBS_ORDER_MOVE_TOP,
+    Bottom => OBS_ORDER_MOVE_BOTTOM,
+});
+
 #[deprecated = "use `SourceRef` instead"]
 pub type SourceContext = SourceRef;
 
@@ -118,7 +145,112 @@ impl_ptr_wrapper!(
     obs_source_release
 );
 
+/// A reference to a source which doesn't keep it alive, see
+/// [`SourceRef::downgrade`].
+///
+/// See [OBS documentation](https://obsproject.com/docs/reference-sources.html#c.obs_weak_source_t)
+pub struct WeakSourceRef {
+    inner: *mut obs_weak_source_t,
+}
+
+impl_ptr_wrapper!(
+    @ptr: inner,
+    WeakSourceRef,
+    obs_weak_source_t,
+    @addref: obs_weak_source_addref,
+    obs_weak_source_release
+);
+
+impl WeakSourceRef {
+    /// Returns the source if it wasn't destroyed yet.
+    pub fn upgrade(&self) -> Option<SourceRef> {
+        unsafe { SourceRef::from_raw_unchecked(obs_weak_source_get_source(self.inner)) }
+    }
+
+    pub fn expired(&self) -> bool {
+        unsafe { obs_weak_source_expired(self.inner) }
+    }
+
+    pub fn references(&self, source: &SourceRef) -> bool {
+        unsafe { obs_weak_source_references_source(self.inner, source.inner) }
+    }
+}
+
+unsafe extern "C" fn enum_sources_proc(params: *mut c_void, source: *mut obs_source_t) -> bool {
+    let sources = unsafe { &mut *(params as *mut Vec<SourceRef>) };
+    // Referenced while OBS holds the list lock, so the source can't be
+    // destroyed in between.
+    sources.extend(SourceRef::from_raw(source));
+    true
+}
+
+unsafe extern "C" fn enum_filters_proc(
+    _parent: *mut obs_source_t,
+    filter: *mut obs_source_t,
+    params: *mut c_void,
+) {
+    let filters = unsafe { &mut *(params as *mut Vec<SourceRef>) };
+    filters.extend(SourceRef::from_raw(filter));
+}
+
 impl SourceRef {
+    /// Creates a source which is saved with the scene collection and listed
+    /// in the UI.
+    pub fn create(id: ObsString, name: ObsString, settings: Option<DataObj<'_>>) -> Result<Self> {
+        let settings = match settings {
+            Some(data) => unsafe { data.as_ptr_mut() },
+            None => std::ptr::null_mut(),
+        };
+        let source = unsafe {
+            obs_source_create(id.as_ptr(), name.as_ptr(), settings, std::ptr::null_mut())
+        };
+
+        unsafe { Self::from_raw_unchecked(source) }.ok_or(Error::NulPointer("obs_source_create"))
+    }
+
+    /// Creates a source which is neither saved nor listed in the UI, e.g. a
+    /// child of another source.
+    pub fn create_private(
+        id: ObsString,
+        name: ObsString,
+        settings: Option<DataObj<'_>>,
+    ) -> Result<Self> {
+        let settings = match settings {
+            Some(data) => unsafe { data.as_ptr_mut() },
+            None => std::ptr::null_mut(),
+        };
+        let source = unsafe { obs_source_create_private(id.as_ptr(), name.as_ptr(), settings) };
+
+        unsafe { Self::from_raw_unchecked(source) }
+            .ok_or(Error::NulPointer("obs_source_create_private"))
+    }
+
+    pub fn by_name(name: ObsString) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_get_source_by_name(name.as_ptr())) }
+    }
+
+    pub fn by_uuid(uuid: ObsString) -> Option<Self> {
+        unsafe { Self::from_raw_unchecked(obs_get_source_by_uuid(uuid.as_ptr())) }
+    }
+
+    /// All public sources, excluding scenes, transitions and private
+    /// sources.
+    pub fn all_sources() -> Vec<Self> {
+        let mut sources = Vec::<SourceRef>::new();
+        unsafe {
+            obs_enum_sources(
+                Some(enum_sources_proc),
+                &mut sources as *mut Vec<SourceRef> as *mut c_void,
+            );
+        }
+        sources
+    }
+
+    pub fn downgrade(&self) -> WeakSourceRef {
+        let weak = unsafe { obs_source_get_weak_source(self.inner) };
+        unsafe { WeakSourceRef::from_raw_unchecked(weak) }.expect("obs_source_get_weak_source")
+    }
+
     /// Run a function on the next source in the filter chain.
     ///
     /// Note: only works with sources that are filters.
@@ -134,6 +266,60 @@ impl SourceRef {
         }
     }
 
+    /// The filters of the source, in the order they are applied.
+    pub fn filters(&self) -> Vec<SourceRef> {
+        let mut filters = Vec::<SourceRef>::new();
+        unsafe {
+            obs_source_enum_filters(
+                self.inner,
+                Some(enum_filters_proc),
+                &mut filters as *mut Vec<SourceRef> as *mut c_void,
+            );
+        }
+        filters
+    }
+
+    pub fn filter_count(&self) -> usize {
+        unsafe { obs_source_filter_count(self.inner) }
+    }
+
+    pub fn filter_by_name(&self, name: ObsString) -> Option<SourceRef> {
+        unsafe {
+            Self::from_raw_unchecked(obs_source_get_filter_by_name(self.inner, name.as_ptr()))
+        }
+    }
+
+    /// Adds `filter` at the end of the filters of the source. It is
+    /// referenced by the source until removed.
+    pub fn add_filter(&mut self, filter: &SourceRef) {
+        unsafe { obs_source_filter_add(self.inner, filter.inner) }
+    }
+
+    pub fn remove_filter(&mut self, filter: &SourceRef) {
+        unsafe { obs_source_filter_remove(self.inner, filter.inner) }
+    }
+
+    pub fn set_filter_order(&mut self, filter: &SourceRef, movement: OrderMovement) {
+        unsafe { obs_source_filter_set_order(self.inner, filter.inner, movement.as_raw()) }
+    }
+
+    /// The position of `filter` among the filters of the source, if it is
+    /// one of them.
+    pub fn filter_index(&self, filter: &SourceRef) -> Option<usize> {
+        let index = unsafe { obs_source_filter_get_index(self.inner, filter.inner) };
+        usize::try_from(index).ok()
+    }
+
+    pub fn set_filter_index(&mut self, filter: &SourceRef, index: usize) {
+        unsafe { obs_source_filter_set_index(self.inner, filter.inner, index) }
+    }
+
+    /// The source the filter is added to - if the source is a filter.
+    pub fn parent(&self) -> Option<SourceRef> {
+        // doc says "Does not increment the reference."
+        SourceRef::from_raw(unsafe { obs_filter_get_parent(self.inner) })
+    }
+
     /// Return a unique id for the filter
     pub fn id(&self) -> usize {
         self.inner as usize
@@ -167,6 +353,26 @@ impl SourceRef {
         unsafe { obs_source_get_id(self.inner) }.try_into_obs_string()
     }
 
+    /// The type data of the source, if it was created from `D`, see
+    /// [`TypeDataSource`].
+    pub fn type_data<D: TypeDataSource>(&self) -> Option<&D::TypeData> {
+        let id = unsafe { obs_source_get_unversioned_id(self.inner) };
+        if id.is_null()
+            || unsafe { CStr::from_ptr(id) } != unsafe { CStr::from_ptr(D::get_id().as_ptr()) }
+        {
+            return None;
+        }
+        // Only versions of `D` are registered with its id, so the type data
+        // comes from `with_type_data`, the downcast checks its type.
+        unsafe { ffi::type_data::<D>(obs_source_get_type_data(self.inner)) }
+    }
+
+    /// The id of the source which stays the same across sessions, unlike its
+    /// name.
+    pub fn uuid(&self) -> Result<ObsString> {
+        unsafe { obs_source_get_uuid(self.inner) }.try_into_obs_string()
+    }
+
     pub fn name(&self) -> Result<ObsString> {
         unsafe { obs_source_get_name(self.inner) }.try_into_obs_string()
     }
@@ -245,6 +451,52 @@ impl SourceRef {
         }
     }
 
+    /// The color space the source renders in, picked from `preferred` if
+    /// possible, e.g. to render the target of a filter without clipping HDR.
+    pub fn color_space(&self, preferred: &[ColorSpace]) -> ColorSpace {
+        let preferred: Vec<gs_color_space> = preferred.iter().map(ColorSpace::as_raw).collect();
+        let raw =
+            unsafe { obs_source_get_color_space(self.inner, preferred.len(), preferred.as_ptr()) };
+        ColorSpace::from_raw(raw).unwrap_or(ColorSpace::SRGB)
+    }
+
+    /// Outputs a frame of an async video source, built with
+    /// [`SourceInfoBuilder::with_async_video`]. OBS copies the frame, so it
+    /// can be reused for the next one.
+    pub fn output_video(&mut self, frame: &SourceFrame) {
+        let raw = frame.as_raw();
+        unsafe {
+            obs_source_output_video2(self.inner, &raw);
+        }
+    }
+
+    /// Outputs audio of an async audio source, built with
+    /// [`SourceInfoBuilder::with_async_audio`].
+    pub fn output_audio(&mut self, audio: &SourceAudio) {
+        let raw = audio.as_raw();
+        unsafe {
+            obs_source_output_audio(self.inner, &raw);
+        }
+    }
+
+    /// Progress of the transition, from `0.0` to `1.0` - if the source is a
+    /// transition.
+    pub fn transition_time(&self) -> f32 {
+        unsafe { obs_transition_get_time(self.inner) }
+    }
+
+    /// Makes the transition always last `duration_ms`, ignoring the duration
+    /// chosen by the user - if the source is a transition.
+    pub fn transition_enable_fixed(&mut self, enable: bool, duration_ms: u32) {
+        unsafe {
+            obs_transition_enable_fixed(self.inner, enable, duration_ms);
+        }
+    }
+
+    pub fn transition_fixed(&self) -> bool {
+        unsafe { obs_transition_fixed(self.inner) }
+    }
+
     /// Skips the video filter if it's invalid
     pub fn skip_video_filter(&mut self) {
         unsafe {
@@ -315,9 +567,85 @@ impl SourceRef {
     }
 }
 
-pub struct EnumActiveContext {}
+/// A file used by a source which doesn't exist anymore, returned by
+/// [`MissingFilesSource::missing_files`].
+///
+/// OBS lists it in its "Missing Files" dialog, and calls `replace` on the
+/// data of the source which reported it, with the path picked by the user,
+/// or an empty path if the file was cleared.
+pub struct MissingFile<D> {
+    pub(crate) path: ObsString,
+    pub(crate) replace: fn(&mut D, &str),
+}
+
+impl<D> MissingFile<D> {
+    pub fn new(path: impl Into<ObsString>, replace: fn(&mut D, &str)) -> Self {
+        Self {
+            path: path.into(),
+            replace,
+        }
+    }
+
+    pub fn path(&self) -> &ObsString {
+        &self.path
+    }
+}
+
+/// Passed to [`EnumActiveSource::enum_active_sources`] to report the
+/// children of a source which are currently active.
+pub struct EnumActiveContext {
+    parent: *mut obs_source_t,
+    callback: obs_source_enum_proc_t,
+    param: *mut c_void,
+}
+
+impl EnumActiveContext {
+    pub(crate) fn new(
+        parent: *mut obs_source_t,
+        callback: obs_source_enum_proc_t,
+        param: *mut c_void,
+    ) -> Self {
+        Self {
+            parent,
+            callback,
+            param,
+        }
+    }
+
+    pub fn enumerate(&self, child: &SourceRef) {
+        if let Some(callback) = self.callback {
+            unsafe { callback(self.parent, child.inner, self.param) }
+        }
+    }
+}
+
+/// Passed to [`EnumAllSource::enum_all_sources`] to report all children of a
+/// source, including inactive ones.
+pub struct EnumAllContext {
+    parent: *mut obs_source_t,
+    callback: obs_source_enum_proc_t,
+    param: *mut c_void,
+}
 
-pub struct EnumAllContext {}
+impl EnumAllContext {
+    pub(crate) fn new(
+        parent: *mut obs_source_t,
+        callback: obs_source_enum_proc_t,
+        param: *mut c_void,
+    ) -> Self {
+        Self {
+            parent,
+            callback,
+            param,
+        }
+    }
+
+    pub fn enumerate(&self, child: &SourceRef) {
+        if let Some(callback) = self.callback {
+            unsafe { callback(self.parent, child.inner, self.param) }
+        }
+    }
+}
 
 pub struct SourceInfo {
     info: Box<obs_source_info>,
@@ -358,18 +686,40 @@ impl AsMut<obs_source_info> for SourceInfo {
 ///  .enable_create()
 ///  .build();
 /// ```
-pub struct SourceInfoBuilder<D: Sourceable> {
-    __data: PhantomData<D>,
+///
+/// The second parameter tracks whether [`with_type_data`] was called, which
+/// [`GetDefaults2Source`] and [`GetProperties2Source`] require before they
+/// can be enabled.
+///
+/// [`with_type_data`]: SourceInfoBuilder::with_type_data
+pub struct SourceInfoBuilder<D: Sourceable, T = NoTypeData> {
+    __data: PhantomData<(D, T)>,
     info: obs_source_info,
 }
 
+/// State of a [`SourceInfoBuilder`] without type data.
+pub struct NoTypeData;
+
+/// State of a [`SourceInfoBuilder`] after
+/// [`with_type_data`](SourceInfoBuilder::with_type_data).
+pub struct WithTypeData;
+
+/// Implemented by the [`SourceInfoBuilder`] states with type data.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`with_type_data` is required by `GetDefaults2Source` and `GetProperties2Source`"
+)]
+pub trait HasTypeData {}
+
+impl HasTypeData for WithTypeData {}
+
 impl<D: Sourceable> SourceInfoBuilder<D> {
     pub(crate) fn new() -> Self {
         Self {
             __data: PhantomData,
             info: obs_source_info {
                 id: D::get_id().as_ptr(),
-                type_: D::get_type().as_raw(),
+                type_: D::TYPE.as_raw(),
                 create: Some(ffi::create::<D>),
                 destroy: Some(ffi::destroy::<D>),
                 type_data: std::ptr::null_mut(),
@@ -378,15 +728,39 @@ impl<D: Sourceable> SourceInfoBuilder<D> {
         }
     }
 
+    /// Attaches data shared by every instance of the source, see
+    /// [`TypeDataSource`].
+    pub fn with_type_data(mut self, type_data: D::TypeData) -> SourceInfoBuilder<D, WithTypeData>
+    where
+        D: TypeDataSource,
+    {
+        let type_data: ffi::TypeDataBox = Box::new(type_data);
+        self.info.type_data = Box::into_raw(Box::new(type_data)) as *mut c_void;
+        self.info.free_type_data = Some(ffi::free_type_data);
+        SourceInfoBuilder {
+            __data: PhantomData,
+            info: self.info,
+        }
+    }
+}
+
+impl<D: Sourceable, T> SourceInfoBuilder<D, T> {
     pub fn build(mut self) -> SourceInfo {
         if self.info.video_render.is_some() {
             self.info.output_flags |= OBS_SOURCE_VIDEO;
         }
 
-        if self.info.audio_render.is_some() || self.info.filter_audio.is_some() {
+        if self.info.audio_render.is_some()
+            || self.info.audio_mix.is_some()
+            || self.info.filter_audio.is_some()
+        {
             self.info.output_flags |= OBS_SOURCE_AUDIO;
         }
 
+        if self.info.audio_mix.is_some() {
+            self.info.output_flags |= OBS_SOURCE_SUBMIX;
+        }
+
         if self.info.media_get_state.is_some() || self.info.media_play_pause.is_some() {
             self.info.output_flags |= OBS_SOURCE_CONTROLLABLE_MEDIA;
         }
@@ -405,55 +779,295 @@ impl<D: Sourceable> SourceInfoBuilder<D> {
         }
     }
 
+    /// Registers the source as a new version of the one with the same id,
+    /// so existing sources keep using the old version. OBS appends `_v2`,
+    /// `_v3`, ... to the id of versions after 1.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.info.version = version;
+        self
+    }
+
+    /// Hides an old version of the source from the UI, while existing
+    /// sources keep working.
+    pub fn with_obsolete(mut self) -> Self {
+        self.info.output_flags |= OBS_SOURCE_CAP_OBSOLETE;
+        self
+    }
+
     pub fn with_icon(mut self, icon: Icon) -> Self {
         self.info.icon_type = icon.into();
         self
     }
+
+    /// Marks the source as composing the audio of its children in
+    /// [`AudioRenderSource::audio_render`], so OBS renders the audio of the
+    /// active children first.
+    pub fn with_composite(mut self) -> Self {
+        self.info.output_flags |= OBS_SOURCE_COMPOSITE;
+        self
+    }
+
+    /// Marks the source as pushing its audio with
+    /// [`SourceRef::output_audio`].
+    pub fn with_async_audio(mut self) -> Self {
+        self.info.output_flags |= OBS_SOURCE_AUDIO;
+        self
+    }
+
+    /// Marks the source as pushing its video with
+    /// [`SourceRef::output_video`] instead of rendering it.
+    pub fn with_async_video(mut self) -> Self {
+        self.info.output_flags |= OBS_SOURCE_ASYNC_VIDEO;
+        self
+    }
+}
+
+macro_rules! set_source_info_fields {
+    ($info:expr, $f:ident) => {
+        $info.$f = Some(ffi::$f::<D>);
+    };
+    ($info:expr, $f:ident { $($field:ident = $func:ident),* }) => {
+        $($info.$field = Some(ffi::$func::<D>);)*
+    };
 }
 
+/// Adds an `enable_*` method for every trait, and the probes used by
+/// [`source_info!`](crate::source_info) to call it automatically. Called with
+/// the list in `__source_traits!`, where new traits are added.
+///
+/// An entry sets the `obs_source_info` field of the same name, unless the
+/// fields are listed as `{ field = ffi_fn, ... }`. A bound after the trait,
+/// as `name => Trait: Bound`, is required from the state of the builder.
 macro_rules! impl_source_builder {
-    ($($f:ident => $t:ident)*) => ($(
-        item! {
-            impl<D: Sourceable + [<$t>]> SourceInfoBuilder<D> {
-                pub fn [<enable_$f>](mut self) -> Self {
-                    self.info.[<$f>] = Some(ffi::[<$f>]::<D>);
-                    self
+    ($($f:ident => $t:ident $(: $bound:ident)? $({ $($field:ident = $func:ident),* })?)*) => (
+        $(
+            item! {
+                impl<D: Sourceable + [<$t>], T $(: $bound)?> SourceInfoBuilder<D, T> {
+                    pub fn [<enable_$f>](mut self) -> Self {
+                        set_source_info_fields!(self.info, $f $({ $($field = $func),* })?);
+                        self
+                    }
                 }
             }
+        )*
+
+        /// Autoref specialization used by [`source_info!`](crate::source_info):
+        /// calling `(&&Probe::<D>::new()).get_name(builder)` resolves to the
+        /// impl on `&Probe<D>` if `D` implements the trait, and to the no-op
+        /// impl on `Probe<D>` otherwise.
+        #[doc(hidden)]
+        pub mod auto {
+            use std::marker::PhantomData;
+
+            use super::*;
+
+            pub struct Probe<D>(PhantomData<D>);
+
+            impl<D> Probe<D> {
+                #[allow(clippy::new_without_default)]
+                pub fn new() -> Self {
+                    Self(PhantomData)
+                }
+            }
+
+            /// Returned by the `forbid_*` probes for traits which are not
+            /// implemented.
+            pub struct Allowed;
+
+            /// Returned by the `forbid_*` probes for traits which are
+            /// implemented, but can't be used with the declared
+            /// [`SourceType`].
+            pub struct NotAllowedForThisSourceType;
+
+            $(
+                item! {
+                    pub trait [<$t Implemented>]<D: Sourceable> {
+                        fn $f<T $(: $bound)?>(
+                            &self,
+                            builder: SourceInfoBuilder<D, T>,
+                        ) -> SourceInfoBuilder<D, T>;
+                        fn [<forbid_$f>](&self) -> NotAllowedForThisSourceType;
+                    }
+
+                    impl<D: Sourceable + $t> [<$t Implemented>]<D> for &Probe<D> {
+                        fn $f<T $(: $bound)?>(
+                            &self,
+                            builder: SourceInfoBuilder<D, T>,
+                        ) -> SourceInfoBuilder<D, T> {
+                            builder.[<enable_$f>]()
+                        }
+                        fn [<forbid_$f>](&self) -> NotAllowedForThisSourceType {
+                            NotAllowedForThisSourceType
+                        }
+                    }
+
+                    pub trait [<$t Missing>]<D: Sourceable> {
+                        fn $f<T>(&self, builder: SourceInfoBuilder<D, T>) -> SourceInfoBuilder<D, T>;
+                        fn [<forbid_$f>](&self) -> Allowed;
+                    }
+
+                    impl<D: Sourceable> [<$t Missing>]<D> for Probe<D> {
+                        fn $f<T>(&self, builder: SourceInfoBuilder<D, T>) -> SourceInfoBuilder<D, T> {
+                            builder
+                        }
+                        fn [<forbid_$f>](&self) -> Allowed {
+                            Allowed
+                        }
+                    }
+                }
+            )*
         }
-    )*)
+    )
+}
+
+/// Passes every trait a source can implement, as `name => Trait` entries, to
+/// `$callback!` after `$arg`s. This is the single list behind the `enable_*`
+/// methods and [`source_info!`](crate::source_info).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __source_traits {
+    ([$($callback:tt)*] $($arg:ident)*) => {
+        $($callback)*! {
+            $($arg)*
+        get_name => GetNameSource
+        get_width => GetWidthSource
+        get_height => GetHeightSource
+        activate => ActivateSource
+        deactivate => DeactivateSource
+        show => ShowSource
+        hide => HideSource
+        update => UpdateSource
+        save => SaveSource
+        load => LoadSource
+        video_render => VideoRenderSource
+        video_get_color_space => VideoColorSpaceSource
+        missing_files => MissingFilesSource
+        audio_render => AudioRenderSource
+        audio_mix => AudioMixSource
+        get_properties => GetPropertiesSource
+        enum_active_sources => EnumActiveSource
+        enum_all_sources => EnumAllSource
+        transition_start => TransitionStartSource
+        transition_stop => TransitionStopSource
+        transition => TransitionSource {
+            video_render = transition_video_render,
+            audio_render = transition_audio_render
+        }
+        video_tick => VideoTickSource
+        filter_audio => FilterAudioSource
+        filter_video => FilterVideoSource
+        filter_add => FilterAddSource
+        filter_remove => FilterRemoveSource
+        get_defaults => GetDefaultsSource
+        get_defaults2 => GetDefaults2Source: HasTypeData
+        get_properties2 => GetProperties2Source: HasTypeData
+        media_play_pause => MediaPlayPauseSource
+        media_restart => MediaRestartSource
+        media_stop => MediaStopSource
+        media_next => MediaNextSource
+        media_previous => MediaPreviousSource
+        media_get_duration => MediaGetDurationSource
+        media_get_time => MediaGetTimeSource
+        media_set_time => MediaSetTimeSource
+        media_get_state => MediaGetStateSource
+        mouse_wheel => MouseWheelSource
+        mouse_click => MouseClickSource
+        mouse_move => MouseMoveSource
+        key_click => KeyClickSource
+        focus => FocusSource
+        }
+    };
+}
+
+crate::__source_traits!([impl_source_builder]);
+
+/// Builds the [`SourceInfo`] of a source, enabling every trait it
+/// implements instead of calling each `enable_*` method of
+/// [`SourceInfoBuilder`].
+///
+/// The last argument is the [`SourceType`] of [`Sourceable::TYPE`], anything
+/// else fails to compile. Implementing a trait which doesn't fit it, like
+/// [`FilterVideoSource`] for an `Input`, fails to compile with a mismatch
+/// between `Allowed` and `NotAllowedForThisSourceType`.
+///
+/// ```ignore
+/// fn load(&mut self, load_context: &mut LoadContext) -> bool {
+///     let source = source_info!(load_context, AvatarSource, Input);
+///     load_context.register_source(source);
+///     true
+/// }
+/// ```
+///
+/// Sources implementing [`TypeDataSource`] pass their type data last, as
+/// `source_info!(load_context, AvatarSource, Input, type_data = cache)`, which
+/// [`GetDefaults2Source`] and [`GetProperties2Source`] require to compile.
+#[macro_export]
+macro_rules! source_info {
+    ($load_context:expr, $source:ty, $kind:ident $(, type_data = $type_data:expr)?) => {{
+        use $crate::source::auto::*;
+
+        let probe = &&Probe::<$source>::new();
+        $crate::__source_info_forbid!(probe, $kind);
+        const {
+            assert!(
+                matches!(
+                    <$source as $crate::source::Sourceable>::TYPE,
+                    $crate::source::SourceType::$kind
+                ),
+                "`Sourceable::TYPE` doesn't match the source type given to `source_info!`",
+            )
+        };
+
+        let builder = $load_context.create_source_builder::<$source>();
+        $(let builder = builder.with_type_data($type_data);)?
+        $crate::__source_traits!([$crate::__source_info_enable] probe builder);
+        builder.build()
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __source_info_enable {
+    ($probe:ident $builder:ident $($f:ident => $t:ident $(: $bound:ident)? $({ $($field:ident = $func:ident),* })?)*) => {
+        $(let $builder = $probe.$f($builder);)*
+    };
 }
 
-impl_source_builder! {
-    get_name => GetNameSource
-    get_width => GetWidthSource
-    get_height => GetHeightSource
-    activate => ActivateSource
-    deactivate => DeactivateSource
-    update => UpdateSource
-    video_render => VideoRenderSource
-    audio_render => AudioRenderSource
-    get_properties => GetPropertiesSource
-    enum_active_sources => EnumActiveSource
-    enum_all_sources => EnumAllSource
-    transition_start => TransitionStartSource
-    transition_stop => TransitionStopSource
-    video_tick => VideoTickSource
-    filter_audio => FilterAudioSource
-    filter_video => FilterVideoSource
-    get_defaults => GetDefaultsSource
-    media_play_pause => MediaPlayPauseSource
-    media_restart => MediaRestartSource
-    media_stop => MediaStopSource
-    media_next => MediaNextSource
-    media_previous => MediaPreviousSource
-    media_get_duration => MediaGetDurationSource
-    media_get_time => MediaGetTimeSource
-    media_set_time => MediaSetTimeSource
-    media_get_state => MediaGetStateSource
-    mouse_wheel => MouseWheelSource
-    mouse_click => MouseClickSource
-    mouse_move => MouseMoveSource
-    key_click => KeyClickSource
-    focus => FocusSource
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __source_info_forbid {
+    ($probe:ident, Input) => {
+        $crate::__source_info_forbid!($probe, @filter @transition);
+    };
+    ($probe:ident, Scene) => {
+        $crate::__source_info_forbid!($probe, @filter @transition);
+    };
+    ($probe:ident, Filter) => {
+        $crate::__source_info_forbid!($probe, @transition);
+    };
+    ($probe:ident, Transition) => {
+        $crate::__source_info_forbid!($probe, @filter);
+    };
+    ($probe:ident, @filter $($rest:tt)*) => {
+        $crate::__source_info_forbid!(
+            $probe,
+            $($rest)*
+            forbid_filter_audio
+            forbid_filter_video
+            forbid_filter_add
+            forbid_filter_remove
+        );
+    };
+    ($probe:ident, @transition $($rest:tt)*) => {
+        $crate::__source_info_forbid!(
+            $probe,
+            $($rest)*
+            forbid_transition_start
+            forbid_transition_stop
+            forbid_transition
+        );
+    };
+    ($probe:ident, $($f:ident)*) => {
+        $(let _: $crate::source::auto::Allowed = $probe.$f();)*
+    };
 }
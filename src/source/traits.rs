@@ -1,16 +1,26 @@
 use obs_sys::{obs_key_event, obs_mouse_event};
 
 use super::context::{CreatableSourceContext, GlobalContext, VideoRenderContext};
-use super::{EnumActiveContext, EnumAllContext, SourceRef, SourceType};
+use super::transition::TransitionRenderContext;
+use super::{EnumActiveContext, EnumAllContext, MissingFile, SourceRef, SourceType};
 use crate::data::DataObj;
+use crate::graphics::ColorSpace;
 use crate::media::state::MediaState;
-use crate::media::{audio::AudioDataContext, video::VideoDataSourceContext};
+use crate::media::{
+    audio::{AudioDataContext, AudioMixContext, AudioRenderContext},
+    video::VideoDataSourceContext,
+};
 use crate::properties::Properties;
 use crate::string::ObsString;
 
 pub trait Sourceable: Sized {
+    /// The type of the source, a constant so [`source_info!`] can check it at
+    /// compile time.
+    ///
+    /// [`source_info!`]: crate::source_info
+    const TYPE: SourceType;
+
     fn get_id() -> ObsString;
-    fn get_type() -> SourceType;
     fn create(create: &mut CreatableSourceContext<Self>, source: SourceRef) -> Self;
 }
 
@@ -31,6 +41,8 @@ simple_trait!(
     get_height => GetHeightSource -> u32
     activate => ActivateSource
     deactivate => DeactivateSource
+    show => ShowSource
+    hide => HideSource
 );
 
 pub trait UpdateSource: Sized {
@@ -63,18 +75,58 @@ pub trait FocusSource: Sized {
     fn focus(&mut self, focused: bool);
 }
 
+/// Saves runtime state of the source, which isn't part of its settings,
+/// into the scene collection.
+pub trait SaveSource: Sized {
+    fn save(&mut self, settings: &mut DataObj);
+}
+
+/// Restores the state written by [`SaveSource`] when the scene collection is
+/// loaded. Called after all sources have been created.
+pub trait LoadSource: Sized {
+    fn load(&mut self, settings: &mut DataObj);
+}
+
 pub trait VideoRenderSource: Sized {
     fn video_render(&mut self, context: &mut GlobalContext, render: &mut VideoRenderContext);
 }
 
+/// Tells OBS which color space [`VideoRenderSource::video_render`] renders
+/// in. Without it, OBS assumes [`ColorSpace::SRGB`].
+pub trait VideoColorSpaceSource: Sized {
+    /// `preferred` lists the spaces OBS would like, best first, usually
+    /// containing the current [`VideoRenderContext::color_space`].
+    fn video_get_color_space(&mut self, preferred: &[ColorSpace]) -> ColorSpace;
+}
+
+/// Renders the audio of the source. Sources mixing the audio of their
+/// children also need [`SourceInfoBuilder::with_composite`].
+///
+/// [`SourceInfoBuilder::with_composite`]: super::SourceInfoBuilder::with_composite
 pub trait AudioRenderSource: Sized {
-    fn audio_render(&mut self, context: &mut GlobalContext);
+    /// Renders audio into the buffers of every mixer in `audio`. Returns
+    /// `false` if no audio was rendered.
+    fn audio_render(&mut self, context: &mut GlobalContext, audio: &mut AudioRenderContext)
+        -> bool;
+}
+
+/// Mixes the audio of the source, e.g. of its children, into a single
+/// output which OBS then treats like audio output by the source itself.
+pub trait AudioMixSource: Sized {
+    /// Returns `false` if no audio was mixed.
+    fn audio_mix(&mut self, audio: &mut AudioMixContext) -> bool;
 }
 
 pub trait GetPropertiesSource: Sized {
     fn get_properties(&mut self) -> Properties;
 }
 
+/// Reports files referenced by the settings of the source which are missing,
+/// so the user can relocate them.
+pub trait MissingFilesSource: Sized {
+    fn missing_files(&mut self) -> Vec<MissingFile<Self>>;
+}
+
 pub trait VideoTickSource: Sized {
     fn video_tick(&mut self, seconds: f32);
 }
@@ -87,6 +139,28 @@ pub trait EnumAllSource: Sized {
     fn enum_all_sources(&mut self, context: &EnumAllContext);
 }
 
+/// A transition between two sources, registered with
+/// [`SourceType::Transition`].
+pub trait TransitionSource: Sized {
+    /// Renders a frame of the transition, usually with
+    /// [`TransitionRenderContext::render`].
+    fn transition_render(
+        &mut self,
+        context: &mut GlobalContext,
+        transition: &mut TransitionRenderContext,
+    );
+
+    /// Volume of the source transitioning from at progress `t`.
+    fn audio_mix_a(&mut self, t: f32) -> f32 {
+        1.0 - t
+    }
+
+    /// Volume of the source transitioning to at progress `t`.
+    fn audio_mix_b(&mut self, t: f32) -> f32 {
+        t
+    }
+}
+
 simple_trait!(
     transition_start => TransitionStartSource
     transition_stop => TransitionStopSource
@@ -100,6 +174,19 @@ pub trait FilterVideoSource: Sized {
     fn filter_video(&mut self, video: &mut VideoDataSourceContext);
 }
 
+/// Called when the filter is added to `parent`.
+pub trait FilterAddSource: Sized {
+    fn filter_add(&mut self, parent: SourceRef);
+}
+
+/// Called when the filter is removed from `parent`, before it is destroyed.
+///
+/// `parent` is `None` when the filter is removed because the parent itself
+/// is being destroyed, it can't be referenced anymore then.
+pub trait FilterRemoveSource: Sized {
+    fn filter_remove(&mut self, parent: Option<SourceRef>);
+}
+
 pub trait MediaPlayPauseSource: Sized {
     fn play_pause(&mut self, pause: bool);
 }
@@ -116,6 +203,27 @@ pub trait GetDefaultsSource {
     fn get_defaults(settings: &mut DataObj);
 }
 
+/// Data shared by every instance of the source type, like a cache, attached
+/// with [`SourceInfoBuilder::with_type_data`] and freed on shutdown. OBS
+/// calls the source from several threads, so the data has to be shareable.
+///
+/// [`SourceInfoBuilder::with_type_data`]: super::SourceInfoBuilder::with_type_data
+pub trait TypeDataSource: Sourceable {
+    type TypeData: Send + Sync + 'static;
+}
+
+/// Like [`GetDefaultsSource`], with the type data of the source.
+pub trait GetDefaults2Source: TypeDataSource {
+    fn get_defaults2(type_data: &Self::TypeData, settings: &mut DataObj);
+}
+
+/// Like [`GetPropertiesSource`], with the type data of the source.
+pub trait GetProperties2Source: TypeDataSource {
+    /// `data` is `None` when OBS asks for the properties of the type instead
+    /// of an instance, buttons don't do anything then.
+    fn get_properties2(data: Option<&mut Self>, type_data: &Self::TypeData) -> Properties;
+}
+
 simple_trait!(
     restart => MediaRestartSource
     stop => MediaStopSource
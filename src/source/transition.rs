@@ -0,0 +1,88 @@
+use std::cell::Cell;
+
+use obs_sys::{gs_texture_t, obs_source_t, obs_transition_get_time, obs_transition_video_render};
+
+use crate::graphics::GraphicsTexture;
+
+/// The two sources of a transition rendered to textures, passed to
+/// [`TransitionRenderContext::render`].
+pub struct TransitionTextures<'a> {
+    /// The source transitioning from, if any.
+    pub a: Option<&'a GraphicsTexture>,
+    /// The source transitioning to, if any.
+    pub b: Option<&'a GraphicsTexture>,
+    /// Progress of the transition, from `0.0` to `1.0`.
+    pub t: f32,
+    pub width: u32,
+    pub height: u32,
+}
+
+type RenderFn<'a> = dyn FnMut(&TransitionTextures<'_>) + 'a;
+
+thread_local! {
+    /// The closure passed to [`TransitionRenderContext::render`], since OBS
+    /// calls the render callback with the data of the source instead of a
+    /// custom param.
+    static RENDER: Cell<Option<*mut RenderFn<'static>>> = const { Cell::new(None) };
+}
+
+/// Passed to [`TransitionSource::transition_render`].
+///
+/// [`TransitionSource::transition_render`]: super::traits::TransitionSource::transition_render
+pub struct TransitionRenderContext {
+    source: *mut obs_source_t,
+}
+
+impl TransitionRenderContext {
+    pub(crate) fn new(source: *mut obs_source_t) -> Self {
+        Self { source }
+    }
+
+    /// Progress of the transition, from `0.0` to `1.0`.
+    pub fn time(&self) -> f32 {
+        unsafe { obs_transition_get_time(self.source) }
+    }
+
+    /// Renders both sources of the transition to textures and calls `func`
+    /// with them. If the transition isn't running, OBS draws the active
+    /// source directly and `func` isn't called.
+    pub fn render<F: FnOnce(&TransitionTextures)>(&mut self, func: F) {
+        let mut func = Some(func);
+        let mut call = |textures: &TransitionTextures| {
+            if let Some(func) = func.take() {
+                func(textures);
+            }
+        };
+        let call: &mut RenderFn<'_> = &mut call;
+        // The closure is only reachable while OBS renders below.
+        let call: *mut RenderFn<'static> = unsafe { std::mem::transmute(call) };
+        let previous = RENDER.replace(Some(call));
+        unsafe { obs_transition_video_render(self.source, Some(render_callback)) };
+        RENDER.set(previous);
+    }
+}
+
+unsafe extern "C" fn render_callback(
+    _data: *mut std::os::raw::c_void,
+    a: *mut gs_texture_t,
+    b: *mut gs_texture_t,
+    t: f32,
+    cx: u32,
+    cy: u32,
+) {
+    let Some(call) = RENDER.get() else {
+        return;
+    };
+    let texture = |raw: *mut gs_texture_t| {
+        (!raw.is_null()).then(|| unsafe { GraphicsTexture::from_raw_borrowed(raw) })
+    };
+    let (a, b) = (texture(a), texture(b));
+    let textures = TransitionTextures {
+        a: a.as_deref(),
+        b: b.as_deref(),
+        t,
+        width: cx,
+        height: cy,
+    };
+    unsafe { (*call)(&textures) }
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "derive")]
+
+use obs_wrapper::{data::DataObj, obs_string, settings::ObsSettings, string::ObsString};
+
+#[derive(Debug, PartialEq, ObsSettings)]
+struct Settings {
+    #[obs(default = true)]
+    enabled: bool,
+    small: i8,
+    #[obs(min = 1, max = 10, step = 1, slider, suffix = " px")]
+    width: i32,
+    wide: i64,
+    #[obs(default = 60, max = 240)]
+    unsigned: u32,
+    huge: u64,
+    size: usize,
+    #[obs(default = 0.5, min = 0.0, max = 1.0, step = 0.05, slider)]
+    opacity: f32,
+    scale: f64,
+    #[obs(name = "file_path", path = "file", filter = "Images (*.png)")]
+    path: String,
+    #[obs(default = "hello".into(), multiline, tooltip = "Shown in the source")]
+    text: String,
+    label: ObsString,
+    #[obs(list("One" = 1, "Two" = 2), default = 2)]
+    choice: i64,
+    #[obs(list("Fill" = "fill", "Fit" = "fit"), default = "fit".to_string())]
+    mode: String,
+    #[obs(color, default = 0xffffff)]
+    color: u32,
+    #[obs(color_alpha)]
+    color_alpha: u32,
+    #[obs(skip)]
+    cache: Vec<u8>,
+}
+
+#[test]
+fn properties() {
+    let mut props = Settings::properties();
+    for name in [
+        "enabled",
+        "small",
+        "width",
+        "wide",
+        "unsigned",
+        "huge",
+        "size",
+        "opacity",
+        "scale",
+        "file_path",
+        "text",
+        "label",
+        "choice",
+        "mode",
+        "color",
+        "color_alpha",
+    ] {
+        assert!(props.get(name).is_some(), "missing property `{}`", name);
+    }
+    assert!(props.get("cache").is_none());
+}
+
+#[test]
+fn defaults() {
+    let mut data = DataObj::new();
+    Settings::set_defaults(&mut data).unwrap();
+
+    assert_eq!(data.get_default::<bool>(obs_string!("enabled")), Some(true));
+    assert_eq!(data.get_default::<u32>(obs_string!("unsigned")), Some(60));
+    assert_eq!(data.get_default::<f32>(obs_string!("opacity")), Some(0.5));
+    assert_eq!(
+        data.get_default::<String>(obs_string!("text")).as_deref(),
+        Some("hello")
+    );
+    assert_eq!(
+        data.get_default::<String>(obs_string!("label")).as_deref(),
+        Some("")
+    );
+    assert_eq!(data.get_default::<i64>(obs_string!("choice")), Some(2));
+}
+
+#[test]
+fn from_settings() {
+    let mut data = DataObj::new();
+    Settings::set_defaults(&mut data).unwrap();
+    data.set::<i64>(obs_string!("width"), 7).unwrap();
+    data.set::<String>(obs_string!("file_path"), "/tmp/image.png")
+        .unwrap();
+    data.set::<ObsString>(obs_string!("label"), obs_string!("label"))
+        .unwrap();
+
+    let mut settings = Settings::from_settings(&data);
+    // `ObsString` compares the variant too, and read strings are never static.
+    assert_eq!(settings.label.as_str(), "label");
+    settings.label = obs_string!("label");
+    assert_eq!(
+        settings,
+        Settings {
+            enabled: true,
+            small: 0,
+            width: 7,
+            wide: 0,
+            unsigned: 60,
+            huge: 0,
+            size: 0,
+            opacity: 0.5,
+            scale: 0.0,
+            path: "/tmp/image.png".to_string(),
+            text: "hello".to_string(),
+            label: obs_string!("label"),
+            choice: 2,
+            mode: "fit".to_string(),
+            color: 0xffffff,
+            color_alpha: 0,
+            cache: Vec::new(),
+        }
+    );
+}
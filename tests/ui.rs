@@ -0,0 +1,7 @@
+#![cfg(feature = "derive")]
+
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
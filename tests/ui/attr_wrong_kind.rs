@@ -0,0 +1,27 @@
+use obs_wrapper::settings::ObsSettings;
+
+#[derive(ObsSettings)]
+struct Password {
+    #[obs(password)]
+    count: i32,
+}
+
+#[derive(ObsSettings)]
+struct Slider {
+    #[obs(slider)]
+    text: String,
+}
+
+#[derive(ObsSettings)]
+struct Color {
+    #[obs(color)]
+    opacity: f32,
+}
+
+#[derive(ObsSettings)]
+struct ListRange {
+    #[obs(list("One" = 1, "Two" = 2), min = 1)]
+    choice: i64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use obs_wrapper::settings::ObsSettings;
+
+#[derive(ObsSettings)]
+struct Settings {
+    width: i32,
+    #[obs(name = "width")]
+    height: i32,
+}
+
+fn main() {}
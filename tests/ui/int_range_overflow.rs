@@ -0,0 +1,15 @@
+use obs_wrapper::settings::ObsSettings;
+
+#[derive(ObsSettings)]
+struct Max {
+    #[obs(max = 5_000_000_000)]
+    huge: u64,
+}
+
+#[derive(ObsSettings)]
+struct Min {
+    #[obs(min = -3_000_000_000)]
+    wide: i64,
+}
+
+fn main() {}